@@ -0,0 +1,25 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustybattler::{Board, gen_legal_moves};
+
+// from_fen has to turn any input into a board or an error, never a panic.
+// whatever it accepts must also survive validate() and to_fen()
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(board) = Board::from_fen(text) else {
+        return;
+    };
+
+    let fen = board.to_fen();
+    if board.validate().is_err() {
+        return;
+    }
+
+    // a sane position can be played from, and writes out a fen that reads back the same
+    gen_legal_moves(&board);
+    let reloaded = Board::from_fen(&fen).expect("to_fen wrote an unreadable fen");
+    assert_eq!(reloaded.to_fen(), fen);
+});
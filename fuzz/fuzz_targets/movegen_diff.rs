@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustybattler::movegen::reference::gen_reference_moves;
+use rustybattler::{Board, chess960_fen, gen_legal_moves};
+
+// plays a random game like random_moves and checks the bitboard generator
+// against the mailbox-scanning reference at every position along the way
+fuzz_target!(|data: &[u8]| {
+    let Some((&index, moves)) = data.split_first_chunk::<2>() else {
+        return;
+    };
+
+    let mut board = Board::get_empty_board();
+    board.set_chess960(true);
+    board.load_fen(&chess960_fen(u16::from_be_bytes(index) % 960));
+
+    for &byte in moves {
+        let mut fast = gen_legal_moves(&board);
+        let mut slow = gen_reference_moves(&board);
+        fast.sort_unstable();
+        slow.sort_unstable();
+        assert_eq!(fast, slow, "{}", board.to_fen());
+
+        if fast.is_empty() {
+            break;
+        }
+        board.make_move(fast[byte as usize % fast.len()]);
+    }
+});
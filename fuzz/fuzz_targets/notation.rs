@@ -0,0 +1,31 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustybattler::{Board, gen_legal_moves, move_to_san, move_to_uci, parse_san, parse_uci};
+
+// input is a fen, a newline and then the move text. the parsers may turn the
+// text down but must not panic, and anything they accept has to be legal and
+// come back unchanged through move_to_san/move_to_uci
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let (fen, mv) = text.split_once('\n').unwrap_or((rustybattler::STARTING_FEN, text));
+    let Ok(board) = Board::from_fen(fen) else {
+        return;
+    };
+    if board.validate().is_err() {
+        return;
+    }
+
+    let legal = gen_legal_moves(&board);
+
+    if let Some(m) = parse_san(&board, mv) {
+        assert!(legal.contains(&m), "parse_san accepted an illegal {mv}");
+        assert_eq!(parse_san(&board, &move_to_san(&board, m)), Some(m));
+    }
+    if let Some(m) = parse_uci(&board, mv) {
+        assert!(legal.contains(&m), "parse_uci accepted an illegal {mv}");
+        assert_eq!(parse_uci(&board, &move_to_uci(&board, m)), Some(m));
+    }
+});
@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustybattler::{Board, chess960_fen, gen_legal_moves};
+
+// the first two bytes pick a chess960 starting position (518 is the standard
+// one), every byte after that picks one of the legal moves. make_move checks
+// the mailbox against the bitboards itself through the consistency-checks feature
+fuzz_target!(|data: &[u8]| {
+    let Some((&index, moves)) = data.split_first_chunk::<2>() else {
+        return;
+    };
+
+    let mut board = Board::get_empty_board();
+    board.set_chess960(true);
+    board.load_fen(&chess960_fen(u16::from_be_bytes(index) % 960));
+
+    for &byte in moves {
+        let legal = gen_legal_moves(&board);
+        if legal.is_empty() {
+            break;
+        }
+        board.make_move(legal[byte as usize % legal.len()]);
+
+        let fen = board.to_fen();
+        assert_eq!(board.validate(), Ok(()), "{fen}");
+
+        // what we write out has to load back into the same position
+        let mut reloaded = Board::get_empty_board();
+        reloaded.set_chess960(true);
+        reloaded.load_fen(&fen);
+        assert_eq!(reloaded.to_fen(), fen);
+    }
+});
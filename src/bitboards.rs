@@ -1,49 +1,201 @@
-pub type Bitboard = u64;
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr};
 
-pub trait BitboardExt {
-    fn print(&self);
-    fn count(&self) -> i32;
-    fn pop_lsb(&mut self) -> usize;
+use crate::square::{File, Square};
+
+// bit n is square n, so a1 is the lowest bit and h8 the highest
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Bitboard(pub(crate) u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+    pub const FULL: Bitboard = Bitboard(!0);
+
+    pub const fn new(bits: u64) -> Self {
+        Bitboard(bits)
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, sq: Square) -> bool {
+        !(self & sq.bb()).is_empty()
+    }
+
+    pub fn popcount(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn lsb(self) -> Option<Square> {
+        Square::from_index(self.0.trailing_zeros() as u8)
+    }
+
+    pub fn pop_lsb(&mut self) -> Option<Square> {
+        let sq = self.lsb()?;
+        *self ^= sq.bb();
+        Some(sq)
+    }
+
+    // one step in each direction. squares pushed off the board are dropped
+    // rather than wrapping round to the other side
+    pub fn north(self) -> Self {
+        self << 8
+    }
+
+    pub fn south(self) -> Self {
+        self >> 8
+    }
+
+    pub fn east(self) -> Self {
+        (self & !File::H.bb()) << 1
+    }
+
+    pub fn west(self) -> Self {
+        (self & !File::A.bb()) >> 1
+    }
+
+    pub fn north_east(self) -> Self {
+        (self & !File::H.bb()) << 9
+    }
+
+    pub fn north_west(self) -> Self {
+        (self & !File::A.bb()) << 7
+    }
+
+    pub fn south_east(self) -> Self {
+        (self & !File::H.bb()) >> 7
+    }
+
+    pub fn south_west(self) -> Self {
+        (self & !File::A.bb()) >> 9
+    }
 }
 
-impl BitboardExt for Bitboard {
-    fn print(&self) {
-        let mut s: String = "\n".to_string();
-        for rank in (0..8).rev() {
-            for file in 0..8 {
-                let index = (rank * 8 + file) as u64;
-                let bit = (1 as u64) << index;
+impl BitAnd for Bitboard {
+    type Output = Self;
 
-                if file == 0 {
-                    s += &(char::from_digit(rank + 1, 10).unwrap().to_string());
-                }
+    fn bitand(self, rhs: Self) -> Self {
+        Bitboard(self.0 & rhs.0)
+    }
+}
 
-                if (*self & bit) > 0 {
-                    s += " # ";
-                } else {
-                    s += " . ";
-                }
-            }
+impl BitOr for Bitboard {
+    type Output = Self;
 
-            s += "\n";
-        }
+    fn bitor(self, rhs: Self) -> Self {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Self;
 
-        s += "  a  b  c  d  e  f  g  h";
-        println!("{s}");
+    fn bitxor(self, rhs: Self) -> Self {
+        Bitboard(self.0 ^ rhs.0)
     }
-    fn count(&self) -> i32 {
-        let mut count = 0;
-        for i in 0..64 {
-            if (*self & (1 << i)) > 0 {
-                count += 1;
+}
+
+impl Not for Bitboard {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Bitboard(!self.0)
+    }
+}
+
+impl Shl<u32> for Bitboard {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self {
+        Bitboard(self.0 << rhs)
+    }
+}
+
+impl Shr<u32> for Bitboard {
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self {
+        Bitboard(self.0 >> rhs)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl From<Square> for Bitboard {
+    fn from(sq: Square) -> Self {
+        sq.bb()
+    }
+}
+
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Bitboard::EMPTY, |bb, sq| bb | sq.bb())
+    }
+}
+
+// squares come out from a1 upwards
+pub struct Squares(Bitboard);
+
+impl Iterator for Squares {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> {
+        self.0.pop_lsb()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.0.popcount() as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Squares {}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = Squares;
+
+    fn into_iter(self) -> Squares {
+        Squares(self)
+    }
+}
+
+impl fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for rank in (0..8).rev() {
+            write!(f, "{}", rank + 1)?;
+            for file in 0..8 {
+                let bit = 1_u64 << (rank * 8 + file);
+                if self.0 & bit > 0 {
+                    write!(f, " # ")?;
+                } else {
+                    write!(f, " . ")?;
+                }
             }
+            writeln!(f)?;
         }
-        count
+        write!(f, "  a  b  c  d  e  f  g  h")
     }
-    fn pop_lsb(&mut self) -> usize {
-        let trailers = self.trailing_zeros() as usize;
-        if trailers == 64 {return 65;}
-        *self ^= (1 as u64) << trailers;
-        trailers
-    }
-}
\ No newline at end of file
+}
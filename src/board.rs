@@ -1,81 +1,144 @@
-use crate::bitboards::{Bitboard, BitboardExt};
-use crate::enums::{Color, PieceType};
+use std::fmt;
+
+use crate::bitboards::Bitboard;
+use crate::castling::CastlingRights;
+use crate::enums::{CastleSide, Color, PieceType};
 use crate::movegen::attack_vectors::{
     BISHOP_AVECS, CROWNIES_AVECS, HORSEY_AVECS, PAWN_AVECS, ROOK_AVECS,
 };
-use crate::movegen::pseudolegals::gen_file_mask;
+use crate::movegen::move_rep::{Move, MoveExt};
+use crate::square::{File, Rank, Square};
 
+// what sits on one square of the mailbox, PieceType::None when it's empty
 #[derive(Copy, Clone)]
-pub struct Square {
+pub struct Piece {
     pub piece: PieceType,
     pub color: Color,
 }
 
-pub fn idx_to_file_rank(idx: u8) -> (u8, u8) {
-    let file = (idx as u8) & (7 as u8);
-    let rank = (idx as u8) >> 3;
-    (file, rank)
-}
-
-pub fn idx_from_file_rank(file: u8, rank: u8) -> u8 {
-    rank << 3 | file
+impl Piece {
+    pub const EMPTY: Piece = Piece {
+        piece: PieceType::None,
+        color: Color::None,
+    };
 }
 
 #[derive(Clone, Copy)]
 pub struct Board {
-    pub wtm: bool,
+    pub(crate) wtm: bool,
+
+    pub(crate) reversables: i32,
+    pub(crate) halfmove_clock: i32,
+    pub(crate) castling_rights: CastlingRights,
+    pub(crate) ep_square: Option<Square>,
+    pub(crate) chess960: bool,
+
+    pub(crate) pawn_bbs: [Bitboard; 2],
+    pub(crate) knight_bbs: [Bitboard; 2],
+    pub(crate) bishop_bbs: [Bitboard; 2],
+    pub(crate) rook_bbs: [Bitboard; 2],
+    pub(crate) queen_bbs: [Bitboard; 2],
+    pub(crate) king_bbs: [Bitboard; 2],
 
-    pub reversables: i32,
-    pub halfmove_clock: i32,
-    pub castling_rights: u8,
+    pub(crate) mailbox: [Piece; 64],
 
-    pub pawn_bbs: [Bitboard; 2],
-    pub knight_bbs: [Bitboard; 2],
-    pub bishop_bbs: [Bitboard; 2],
-    pub rook_bbs: [Bitboard; 2],
-    pub queen_bbs: [Bitboard; 2],
-    pub king_bbs: [Bitboard; 2],
+    pub(crate) white_bb: Bitboard,
+    pub(crate) black_bb: Bitboard,
+}
 
-    pub mailbox: [Square; 64],
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FenError {
+    Placement,
+    SideToMove,
+    Castling,
+    EnPassant,
+    Counters,
+    TooManyFields,
+}
 
-    pub white_bb: Bitboard,
-    pub black_bb: Bitboard,
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let field = match self {
+            FenError::Placement => "piece placement",
+            FenError::SideToMove => "side to move",
+            FenError::Castling => "castling rights",
+            FenError::EnPassant => "en passant square",
+            FenError::Counters => "move counters",
+            FenError::TooManyFields => "trailing fields",
+        };
+        write!(f, "bad fen: can't read the {field}")
+    }
 }
 
+impl std::error::Error for FenError {}
+
 pub static STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 // a1-h8
 impl Board {
     pub fn get_empty_board() -> Self {
-        return Self {
+        Self {
             wtm: true,
             reversables: 0,
             halfmove_clock: 0,
-            castling_rights: 0b1111,
-            pawn_bbs: [0; 2],
-            knight_bbs: [0; 2],
-            bishop_bbs: [0; 2],
-            rook_bbs: [0; 2],
-            queen_bbs: [0; 2],
-            king_bbs: [0; 2],
-            mailbox: [Square {
-                piece: PieceType::None,
-                color: Color::None,
-            }; 64],
-            white_bb: 0,
-            black_bb: 0,
-        };
+            castling_rights: CastlingRights::standard(),
+            ep_square: None,
+            chess960: false,
+            pawn_bbs: [Bitboard::EMPTY; 2],
+            knight_bbs: [Bitboard::EMPTY; 2],
+            bishop_bbs: [Bitboard::EMPTY; 2],
+            rook_bbs: [Bitboard::EMPTY; 2],
+            queen_bbs: [Bitboard::EMPTY; 2],
+            king_bbs: [Bitboard::EMPTY; 2],
+            mailbox: [Piece::EMPTY; 64],
+            white_bb: Bitboard::EMPTY,
+            black_bb: Bitboard::EMPTY,
+        }
     }
 
     pub fn init(&mut self) {
         self.load_fen(STARTING_FEN);
     }
 
-    pub fn add_piece(&mut self, color: Color, piece: PieceType, index: usize) {
+    pub fn side_to_move(&self) -> Color {
+        if self.wtm { Color::White } else { Color::Black }
+    }
+
+    pub fn piece_at(&self, sq: Square) -> Piece {
+        self.mailbox[sq.index()]
+    }
+
+    // only changes how castling is written, the rules are the same either way
+    pub fn chess960(&self) -> bool {
+        self.chess960
+    }
+
+    pub fn set_chess960(&mut self, chess960: bool) {
+        self.chess960 = chess960;
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    pub fn ep_square(&self) -> Option<Square> {
+        self.ep_square
+    }
+
+    // plies since the last capture or pawn move, for the fifty-move rule
+    pub fn fifty_move_counter(&self) -> u32 {
+        self.reversables as u32
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        (self.halfmove_clock / 2 + 1) as u32
+    }
+
+    pub(crate) fn add_piece(&mut self, color: Color, piece: PieceType, sq: Square) {
         let is_white = color == Color::White;
         let color_idx = color as usize;
 
-        let bb = (1 as u64) << index;
+        let bb = sq.bb();
 
         if is_white {
             self.white_bb |= bb;
@@ -83,7 +146,8 @@ impl Board {
             self.black_bb |= bb;
         }
 
-        self.mailbox[index].color = color;
+        self.mailbox[sq.index()].color = color;
+        self.mailbox[sq.index()].piece = piece;
 
         match piece {
             PieceType::Pawn => {
@@ -108,16 +172,12 @@ impl Board {
         }
     }
 
-    pub fn remove_piece(&mut self, index: usize) -> (Color, PieceType) {
-        let color = self.mailbox[index].color;
-        let piece = self.mailbox[index].piece;
+    pub(crate) fn remove_piece(&mut self, sq: Square) -> (Color, PieceType) {
+        let Piece { piece, color } = self.piece_at(sq);
 
-        let bb = (1 as u64) << index;
+        let bb = sq.bb();
 
-        self.mailbox[index] = Square {
-            piece: PieceType::None,
-            color: Color::None,
-        };
+        self.mailbox[sq.index()] = Piece::EMPTY;
 
         if color == Color::White {
             self.white_bb &= !bb;
@@ -150,275 +210,617 @@ impl Board {
         (color, piece)
     }
 
-    pub fn move_piece(&mut self, from: usize, to: usize) {
+    pub(crate) fn move_piece(&mut self, from: Square, to: Square) {
         let (color, piece) = self.remove_piece(from);
         self.remove_piece(to);
         self.add_piece(color, piece, to);
     }
 
+    // keeps the chess960 setting, which only changes how castling is written,
+    // unless the fen's castling rights can only be chess960.
+    // panics on a malformed fen, use from_fen for anything a user typed in
     pub fn load_fen(&mut self, fen: &str) {
-        let split_fen: Vec<&str> = fen.split(' ').collect();
-        let position: &str = split_fen[0];
-        self.wtm = split_fen[1] == "w";
-
-        self.mailbox = [Square {
-            piece: PieceType::None,
-            color: Color::None,
-        }; 64];
-
-        self.pawn_bbs = [0; 2];
-        self.knight_bbs = [0; 2];
-        self.bishop_bbs = [0; 2];
-        self.rook_bbs = [0; 2];
-        self.queen_bbs = [0; 2];
-        self.king_bbs = [0; 2];
-
-        self.white_bb = 0;
-        self.black_bb = 0;
-
-        self.reversables = 0;
-        self.halfmove_clock = 0;
-        self.castling_rights = 0b1111; // KQkq
-
-        let (mut rank, mut file) = (7, 0);
-        for c in position.chars() {
-            if c == '/' {
-                rank -= 1;
-                file = 0
-            } else if c.is_numeric() {
-                file += c.to_digit(10).unwrap();
-            } else {
-                let is_white = c.is_uppercase();
+        let chess960 = self.chess960;
+        *self = Self::from_fen(fen).unwrap_or_else(|e| panic!("{e}"));
+        self.chess960 |= chess960;
+    }
 
-                let mailbox_idx = (rank * 8 + file) as usize;
-                let index: Bitboard = (1 as Bitboard) << mailbox_idx;
+    // only the format is checked here, validate() is what catches impossible
+    // positions. the castling, en passant and counter fields may be left off
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let mut board = Self::get_empty_board();
+        board.castling_rights = CastlingRights::none();
 
-                if is_white {
-                    self.white_bb |= index;
-                } else {
-                    self.black_bb |= index;
+        let split_fen: Vec<&str> = fen.split_whitespace().collect();
+        if split_fen.len() > 6 {
+            return Err(FenError::TooManyFields);
+        }
+        let position: &str = split_fen.first().ok_or(FenError::Placement)?;
+        board.wtm = match split_fen.get(1) {
+            Some(&"w") => true,
+            Some(&"b") => false,
+            _ => return Err(FenError::SideToMove),
+        };
+
+        let ranks: Vec<&str> = position.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::Placement);
+        }
+        for (row, rank) in Rank::all().rev().zip(ranks) {
+            let mut file = 0;
+            for c in rank.chars() {
+                if let Some(skip @ 1..=8) = c.to_digit(10) {
+                    file += skip as u8;
+                    if file > 8 {
+                        return Err(FenError::Placement);
+                    }
+                    continue;
                 }
 
-                let color = if is_white { Color::White } else { Color::Black };
-                self.mailbox[mailbox_idx].color = color;
+                let sq = Square::new(File::new(file).ok_or(FenError::Placement)?, row);
+                let color = if c.is_uppercase() {
+                    Color::White
+                } else {
+                    Color::Black
+                };
 
-                match c.to_lowercase().next().unwrap() {
+                match c.to_ascii_lowercase() {
                     'p' => {
-                        self.add_piece(color, PieceType::Pawn, mailbox_idx);
+                        board.add_piece(color, PieceType::Pawn, sq);
                     }
                     'n' => {
-                        self.add_piece(color, PieceType::Knight, mailbox_idx);
+                        board.add_piece(color, PieceType::Knight, sq);
                     }
                     'b' => {
-                        self.add_piece(color, PieceType::Bishop, mailbox_idx);
+                        board.add_piece(color, PieceType::Bishop, sq);
                     }
                     'r' => {
-                        self.add_piece(color, PieceType::Rook, mailbox_idx);
+                        board.add_piece(color, PieceType::Rook, sq);
                     }
                     'q' => {
-                        self.add_piece(color, PieceType::Queen, mailbox_idx);
+                        board.add_piece(color, PieceType::Queen, sq);
                     }
                     'k' => {
-                        self.add_piece(color, PieceType::King, mailbox_idx);
+                        board.add_piece(color, PieceType::King, sq);
                     }
                     // ...
                     // lots of tedious code rewriting here
                     _ => {
-                        panic!("bad fen")
+                        return Err(FenError::Placement);
                     }
                 }
                 file += 1;
             }
+            if file != 8 {
+                return Err(FenError::Placement);
+            }
         }
+
+        board.load_castling_rights(split_fen.get(2).copied().unwrap_or("-"))?;
+        // a king off the e-file or an inner rook can't castle in standard chess
+        board.chess960 = !board.castling_is_standard();
+
+        board.ep_square = match split_fen.get(3).copied().unwrap_or("-") {
+            "-" => None,
+            sq => Some(sq.parse().map_err(|_| FenError::EnPassant)?),
+        };
+
+        // reversables is the fifty-move counter, halfmove_clock counts plies from the start
+        let counter = |field: Option<&&str>, default: i32| match field {
+            None => Ok(default),
+            Some(n) => n
+                .parse::<i32>()
+                .ok()
+                .filter(|&n| n >= 0)
+                .ok_or(FenError::Counters),
+        };
+        board.reversables = counter(split_fen.get(4), 0)?;
+        let fullmoves = counter(split_fen.get(5), 1)?;
+        board.halfmove_clock = (fullmoves.max(1) - 1)
+            .checked_mul(2)
+            .and_then(|plies| plies.checked_add((!board.wtm) as i32))
+            .ok_or(FenError::Counters)?;
+
+        Ok(board)
     }
 
-    pub fn get_occupied_squares(&self) -> Bitboard {
-        self.white_bb | self.black_bb
+    // accepts standard KQkq, X-FEN (KQkq plus file letters for inner rooks)
+    // and Shredder-FEN (file letters only). rights that don't fit the position
+    // are kept as written, validate() is what reports them
+    fn load_castling_rights(&mut self, field: &str) -> Result<(), FenError> {
+        if field == "-" {
+            return Ok(());
+        }
+
+        for c in field.chars() {
+            let color = if c.is_uppercase() {
+                Color::White
+            } else {
+                Color::Black
+            };
+            let back_rank = Rank::R1.relative_to(color);
+            let king_file = self
+                .king_square(color)
+                .filter(|sq| sq.rank() == back_rank)
+                .map_or(File::E, |sq| sq.file());
+            let is_rook = |file: File| {
+                let p = self.piece_at(Square::new(file, back_rank));
+                p.piece == PieceType::Rook && p.color == color
+            };
+
+            let (side, rook_file) = match c.to_ascii_lowercase() {
+                'k' => (
+                    CastleSide::King,
+                    File::all()
+                        .rev()
+                        .find(|&f| f > king_file && is_rook(f))
+                        .unwrap_or(File::H),
+                ),
+                'q' => (
+                    CastleSide::Queen,
+                    File::all()
+                        .find(|&f| f < king_file && is_rook(f))
+                        .unwrap_or(File::A),
+                ),
+                f @ 'a'..='h' => {
+                    let file = File::from_char(f).unwrap();
+                    let side = if file > king_file {
+                        CastleSide::King
+                    } else {
+                        CastleSide::Queen
+                    };
+                    (side, file)
+                }
+                _ => return Err(FenError::Castling),
+            };
+
+            self.castling_rights.set(color, side, rook_file);
+        }
+        Ok(())
     }
 
-    pub fn get_knight_targets(&self, color: Color) -> Bitboard {
-        let mut knights = self.knight_bbs[color as usize];
-        let us = [self.white_bb, self.black_bb][color as usize];
-        let mut from = knights.pop_lsb();
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in Rank::all().rev() {
+            let mut empty = 0;
+            for file in File::all() {
+                let p = self.piece_at(Square::new(file, rank));
+                let c = match p.piece {
+                    PieceType::None => {
+                        empty += 1;
+                        continue;
+                    }
+                    PieceType::Pawn => 'p',
+                    PieceType::Knight => 'n',
+                    PieceType::Bishop => 'b',
+                    PieceType::Rook => 'r',
+                    PieceType::Queen => 'q',
+                    PieceType::King => 'k',
+                };
+                if empty > 0 {
+                    fen += &empty.to_string();
+                    empty = 0;
+                }
+                if p.color == Color::White {
+                    fen.push(c.to_ascii_uppercase());
+                } else {
+                    fen.push(c);
+                }
+            }
+            if empty > 0 {
+                fen += &empty.to_string();
+            }
+            if rank > Rank::R1 {
+                fen.push('/');
+            }
+        }
 
-        let mut bb: Bitboard = 0;
+        fen += if self.wtm { " w " } else { " b " };
+        fen += &self.castling_field();
+        fen.push(' ');
+        fen += &self.ep_square.map_or("-".to_string(), |sq| sq.to_string());
+        fen += &format!(" {} {}", self.reversables, self.halfmove_clock / 2 + 1);
 
-        while from != 65 {
-            let mut not_blocked = HORSEY_AVECS[from as usize] & !us;
+        fen
+    }
 
-            let mut index = not_blocked.pop_lsb();
-            while index != 65 {
-                let to = index;
-                bb |= (1 as u64) << to;
-                index = not_blocked.pop_lsb();
+    // Shredder-FEN in chess960 mode, otherwise X-FEN (which is plain KQkq for standard chess)
+    pub(crate) fn castling_field(&self) -> String {
+        let mut field = String::new();
+
+        for color in [Color::White, Color::Black] {
+            let back_rank = Rank::R1.relative_to(color);
+            for side in [CastleSide::King, CastleSide::Queen] {
+                let Some(rook_file) = self.castling_rights.get(color, side) else {
+                    continue;
+                };
+
+                let outermost = !File::all().any(|f| {
+                    let outer = match side {
+                        CastleSide::King => f > rook_file,
+                        CastleSide::Queen => f < rook_file,
+                    };
+                    let p = self.piece_at(Square::new(f, back_rank));
+                    outer && p.piece == PieceType::Rook && p.color == color
+                });
+
+                let c = if self.chess960 || !outermost {
+                    rook_file.to_char()
+                } else if side == CastleSide::King {
+                    'k'
+                } else {
+                    'q'
+                };
+                if color == Color::White {
+                    field.push(c.to_ascii_uppercase());
+                } else {
+                    field.push(c);
+                }
             }
+        }
 
-            from = knights.pop_lsb();
+        if field.is_empty() {
+            field.push('-');
         }
+        field
+    }
 
-        bb
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.king_bbs[color as usize].lsb()
     }
 
-    pub fn get_king_targets(&self, color: Color) -> Bitboard {
-        let mut kings = self.king_bbs[color as usize];
-        let us = [self.white_bb, self.black_bb][color as usize];
+    // only the cases no sequence of moves can mate from: bare kings plus at most one minor
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy = self.pawn_bbs[0]
+            | self.pawn_bbs[1]
+            | self.rook_bbs[0]
+            | self.rook_bbs[1]
+            | self.queen_bbs[0]
+            | self.queen_bbs[1];
+        let minors =
+            self.knight_bbs[0] | self.knight_bbs[1] | self.bishop_bbs[0] | self.bishop_bbs[1];
+        heavy.is_empty() && minors.popcount() <= 1
+    }
+
+    pub fn is_square_attacked(&self, sq: Square, by: Color) -> bool {
+        let c = by as usize;
+        let diagonals = self.bishop_bbs[c] | self.queen_bbs[c];
+        let orthogonals = self.rook_bbs[c] | self.queen_bbs[c];
+
+        let attackers = HORSEY_AVECS[sq.index()] & self.knight_bbs[c]
+            | CROWNIES_AVECS[sq.index()] & self.king_bbs[c]
+            | self.get_bishop_attacks(sq) & diagonals
+            | self.get_rook_attacks(sq) & orthogonals;
+        if !attackers.is_empty() {
+            return true;
+        }
+
+        // pawns attack diagonally forward, so look one rank behind the target
+        let Some(behind) = sq.backward(by) else {
+            return false;
+        };
+        [-1, 1]
+            .into_iter()
+            .filter_map(|df| behind.offset(df, 0))
+            .any(|from| self.pawn_bbs[c].contains(from))
+    }
 
-        let mut bb: Bitboard = 0;
+    pub fn in_check(&self, color: Color) -> bool {
+        self.king_square(color)
+            .is_some_and(|sq| self.is_square_attacked(sq, color.opponent()))
+    }
+
+    // king and rook destinations are the same as in standard chess (g/f or c/d file),
+    // wherever they started
+    pub(crate) fn castling_squares(
+        &self,
+        color: Color,
+        side: CastleSide,
+    ) -> Option<(Square, Square, Square, Square)> {
+        let rook_file = self.castling_rights.get(color, side)?;
+        let back_rank = Rank::R1.relative_to(color);
+        // rights loaded from a bad fen can point anywhere, so only trust them
+        // with the king on the back rank and the rook on the right side of it
+        let king_from = self
+            .king_square(color)
+            .filter(|sq| sq.rank() == back_rank)?;
+        let rook_on_side = match side {
+            CastleSide::King => rook_file > king_from.file(),
+            CastleSide::Queen => rook_file < king_from.file(),
+        };
+        if !rook_on_side {
+            return None;
+        }
+        let rook_from = Square::new(rook_file, back_rank);
+        let (king_to, rook_to) = match side {
+            CastleSide::King => (File::G, File::F),
+            CastleSide::Queen => (File::C, File::D),
+        };
+        Some((
+            king_from,
+            Square::new(king_to, back_rank),
+            rook_from,
+            Square::new(rook_to, back_rank),
+        ))
+    }
 
-        let from = kings.pop_lsb();
+    fn castling_is_standard(&self) -> bool {
+        [Color::White, Color::Black].into_iter().all(|color| {
+            [CastleSide::King, CastleSide::Queen]
+                .into_iter()
+                .all(|side| match self.castling_squares(color, side) {
+                    Some((king_from, _, rook_from, _)) => {
+                        let corner = match side {
+                            CastleSide::King => File::H,
+                            CastleSide::Queen => File::A,
+                        };
+                        king_from.file() == File::E && rook_from.file() == corner
+                    }
+                    // no right, or one validate() will complain about
+                    None => true,
+                })
+        })
+    }
 
-        let mut not_blocked = CROWNIES_AVECS[from] & !us;
+    pub fn can_castle(&self, color: Color, side: CastleSide) -> bool {
+        let Some((king_from, king_to, rook_from, rook_to)) = self.castling_squares(color, side)
+        else {
+            return false;
+        };
 
-        let mut index = not_blocked.pop_lsb();
-        while index != 65 {
-            bb |= (1 as u64) << index;
-            index = not_blocked.pop_lsb();
+        let rook = self.piece_at(rook_from);
+        if rook.piece != PieceType::Rook || rook.color != color || self.in_check(color) {
+            return false;
         }
 
-        bb
+        // every square either piece passes over must be empty, apart from the two castling pieces
+        let lo = king_from.min(king_to).min(rook_from).min(rook_to);
+        let hi = king_from.max(king_to).max(rook_from).max(rook_to);
+        let others = self.get_occupied_squares() & !king_from.bb() & !rook_from.bb();
+        if Square::all().any(|sq| (lo..=hi).contains(&sq) && others.contains(sq)) {
+            return false;
+        }
+
+        // check the king's path with both pieces lifted, so a castling rook
+        // can't hide an attacker on the back rank
+        let opp = color.opponent();
+        let mut lifted = *self;
+        lifted.remove_piece(king_from);
+        lifted.remove_piece(rook_from);
+        let (lo, hi) = (king_from.min(king_to), king_from.max(king_to));
+        !Square::all().any(|sq| (lo..=hi).contains(&sq) && lifted.is_square_attacked(sq, opp))
     }
 
-    fn get_bishop_attacks(&self, index: usize) -> Bitboard {
-        let mut bb: Bitboard = 0;
+    // moves the king and rook and drops that side's castling rights; legality is
+    // checked separately by can_castle. undo by restoring a copy of the board
+    pub(crate) fn castle(&mut self, color: Color, side: CastleSide) {
+        let (king_from, king_to, rook_from, rook_to) = self
+            .castling_squares(color, side)
+            .expect("no castling rights");
 
-        let avec = BISHOP_AVECS[index];
+        self.remove_piece(king_from);
+        self.remove_piece(rook_from);
+        self.add_piece(color, PieceType::King, king_to);
+        self.add_piece(color, PieceType::Rook, rook_to);
 
-        let blockers = avec & self.get_occupied_squares();
+        self.castling_rights.remove_color(color);
+        self.ep_square = None;
+    }
 
-        let (file, rank) = idx_to_file_rank(index as u8);
-
-        // Southwest
-        let mut limiter = std::cmp::min(file, rank);
-        for i in 1..limiter + 1 {
-            let f = file - i;
-            let r = rank - i;
-            let sq = (1 as u64) << idx_from_file_rank(f, r);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
-            }
+    // no legality checks here, the move is expected to come from the move generator.
+    // undo by restoring a copy of the board
+    pub fn make_move(&mut self, m: Move) {
+        let us = self.side_to_move();
+        let (from, to) = (m.from(), m.to());
+        let mover = self.piece_at(from);
+        let target = self.piece_at(to);
+
+        self.halfmove_clock += 1;
+        self.wtm = !self.wtm;
+
+        if mover.piece == PieceType::King && target.piece == PieceType::Rook && target.color == us {
+            let side = if to > from {
+                CastleSide::King
+            } else {
+                CastleSide::Queen
+            };
+            self.castle(us, side);
+            self.reversables += 1;
+            #[cfg(feature = "consistency-checks")]
+            self.assert_consistent();
+            return;
         }
-        // Northwest
-        limiter = std::cmp::min(file, 7 - rank);
-        for i in 1..limiter + 1 {
-            let f = file - i;
-            let r = rank + i;
-            let sq = (1 as u64) << idx_from_file_rank(f, r);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
+
+        let is_capture = target.piece != PieceType::None;
+        let ep_square = self.ep_square.take();
+
+        if mover.piece == PieceType::Pawn {
+            if Some(to) == ep_square {
+                // the captured pawn sits behind the target square
+                self.remove_piece(to.backward(us).unwrap());
             }
-        }
-        // Northeast
-        limiter = std::cmp::min(7 - file, 7 - rank);
-        for i in 1..limiter + 1 {
-            let f = file + i;
-            let r = rank + i;
-            let sq = (1 as u64) << idx_from_file_rank(f, r);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
+            if from.rank().index().abs_diff(to.rank().index()) == 2 {
+                self.ep_square = from.forward(us);
             }
         }
-        // Southeast
-        limiter = std::cmp::min(7 - file, rank);
-        for i in 1..limiter + 1 {
-            let f = file + i;
-            let r = rank - i;
-            let sq = (1 as u64) << idx_from_file_rank(f, r);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
+
+        self.move_piece(from, to);
+
+        if m.promotion() != PieceType::None {
+            self.remove_piece(to);
+            self.add_piece(us, m.promotion(), to);
+        }
+
+        if mover.piece == PieceType::King {
+            self.castling_rights.remove_color(us);
+        }
+        // moving or capturing a castling rook drops that right
+        for color in [Color::White, Color::Black] {
+            for side in [CastleSide::King, CastleSide::Queen] {
+                if let Some(rook_file) = self.castling_rights.get(color, side)
+                    && [from, to].contains(&Square::new(rook_file, Rank::R1.relative_to(color)))
+                {
+                    self.castling_rights.remove(color, side);
+                }
             }
         }
 
-        bb
+        if mover.piece == PieceType::Pawn || is_capture {
+            self.reversables = 0;
+        } else {
+            self.reversables += 1;
+        }
+
+        #[cfg(feature = "consistency-checks")]
+        self.assert_consistent();
     }
 
-    pub fn get_bishop_targets(&self, color: Color) -> Bitboard {
+    // standard chess writes castling as the king's two-square move (e1g1),
+    // chess960 as king-takes-rook (e1h1). king-takes-rook is also used whenever
+    // the king doesn't land two files away, since b1c1 or g1g1 can't be read
+    // as castling
+    pub fn castling_move_str(&self, color: Color, side: CastleSide) -> Option<String> {
+        let (king_from, king_to, rook_from, _) = self.castling_squares(color, side)?;
+        let two_squares = king_from.file().index().abs_diff(king_to.file().index()) == 2;
+        let to = if self.chess960 || !two_squares {
+            rook_from
+        } else {
+            king_to
+        };
+        Some(format!("{king_from}{to}"))
+    }
+
+    pub fn pieces(&self, color: Color, piece: PieceType) -> Bitboard {
+        let bbs = match piece {
+            PieceType::Pawn => self.pawn_bbs,
+            PieceType::Knight => self.knight_bbs,
+            PieceType::Bishop => self.bishop_bbs,
+            PieceType::Rook => self.rook_bbs,
+            PieceType::Queen => self.queen_bbs,
+            PieceType::King => self.king_bbs,
+            PieceType::None => return Bitboard::EMPTY,
+        };
+        match color {
+            Color::White | Color::Black => bbs[color as usize],
+            Color::None => Bitboard::EMPTY,
+        }
+    }
+
+    pub fn get_occupied_squares(&self) -> Bitboard {
+        self.white_bb | self.black_bb
+    }
+
+    pub fn get_color_squares(&self, color: Color) -> Bitboard {
+        match color {
+            Color::White => self.white_bb,
+            Color::Black => self.black_bb,
+            Color::None => Bitboard::EMPTY,
+        }
+    }
+
+    pub fn get_knight_targets(&self, color: Color) -> Bitboard {
+        let knights = self.knight_bbs[color as usize];
         let us = [self.white_bb, self.black_bb][color as usize];
-        let mut bishops = self.bishop_bbs[color as usize];
 
-        let mut bb: Bitboard = 0;
+        let mut bb = Bitboard::EMPTY;
+
+        for from in knights {
+            let not_blocked = HORSEY_AVECS[from.index()] & !us;
 
-        let mut from = bishops.pop_lsb();
-        while from != 65 {
-            let mut moves_bb = self.get_bishop_attacks(from) & !us;
-            let mut to = moves_bb.pop_lsb();
-            while to != 65 {
-                bb |= (1 as u64) << to;
-                to = moves_bb.pop_lsb();
+            for to in not_blocked {
+                bb |= to.bb();
             }
-            from = bishops.pop_lsb();
         }
 
         bb
     }
 
-    fn get_rook_attacks(&self, index: usize) -> Bitboard {
-        let mut bb: Bitboard = 0;
+    pub fn get_king_targets(&self, color: Color) -> Bitboard {
+        let us = [self.white_bb, self.black_bb][color as usize];
 
-        let avec = ROOK_AVECS[index];
+        let mut bb = Bitboard::EMPTY;
 
-        let blockers = avec & self.get_occupied_squares();
+        let Some(from) = self.king_square(color) else {
+            return bb;
+        };
 
-        let (file, rank) = idx_to_file_rank(index as u8);
+        let not_blocked = CROWNIES_AVECS[from.index()] & !us;
 
-        // South
-        for i in 1..rank + 1 {
-            let r = rank - i;
-            let sq = (1 as u64) << idx_from_file_rank(file, r);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
-            }
+        for to in not_blocked {
+            bb |= to.bb();
         }
-        // West
-        for i in 1..file + 1 {
-            let f = file - i;
-            let sq = (1 as u64) << idx_from_file_rank(f, rank);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
-            }
-        }
-        // North
-        for i in 1..8 - rank {
-            let r = rank + i;
-            let sq = (1 as u64) << idx_from_file_rank(file, r);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
+
+        bb
+    }
+
+    // walks each direction until it runs off the board or hits a piece,
+    // which is included so captures show up
+    fn slide(&self, from: Square, avec: Bitboard, directions: [(i8, i8); 4]) -> Bitboard {
+        let mut bb = Bitboard::EMPTY;
+
+        let blockers = avec & self.get_occupied_squares();
+
+        for (files, ranks) in directions {
+            let mut sq = from;
+            while let Some(next) = sq.offset(files, ranks) {
+                bb |= next.bb();
+                if blockers.contains(next) {
+                    break;
+                }
+                sq = next;
             }
         }
-        // East
-        for i in 1..8 - file {
-            let f = file + i;
-            let sq = (1 as u64) << idx_from_file_rank(f, rank);
-            bb |= sq;
-            if sq & blockers > 0 {
-                break;
+
+        bb
+    }
+
+    pub fn get_bishop_attacks(&self, sq: Square) -> Bitboard {
+        // southwest, northwest, northeast, southeast
+        self.slide(
+            sq,
+            BISHOP_AVECS[sq.index()],
+            [(-1, -1), (-1, 1), (1, 1), (1, -1)],
+        )
+    }
+
+    pub fn get_bishop_targets(&self, color: Color) -> Bitboard {
+        let us = [self.white_bb, self.black_bb][color as usize];
+        let bishops = self.bishop_bbs[color as usize];
+
+        let mut bb = Bitboard::EMPTY;
+
+        for from in bishops {
+            let moves_bb = self.get_bishop_attacks(from) & !us;
+            for to in moves_bb {
+                bb |= to.bb();
             }
         }
 
         bb
     }
 
+    pub fn get_rook_attacks(&self, sq: Square) -> Bitboard {
+        // south, west, north, east
+        self.slide(
+            sq,
+            ROOK_AVECS[sq.index()],
+            [(0, -1), (-1, 0), (0, 1), (1, 0)],
+        )
+    }
+
     pub fn get_rook_targets(&self, color: Color) -> Bitboard {
         let us = [self.white_bb, self.black_bb][color as usize];
-        let mut rooks = self.rook_bbs[color as usize];
+        let rooks = self.rook_bbs[color as usize];
 
-        let mut bb: Bitboard = 0;
+        let mut bb = Bitboard::EMPTY;
 
-        let mut from = rooks.pop_lsb();
-        while from != 65 {
-            let mut moves_bb = self.get_rook_attacks(from) & !us;
-            let mut to = moves_bb.pop_lsb();
-            while to != 65 {
-                bb |= (1 as u64) << to;
-                to = moves_bb.pop_lsb();
+        for from in rooks {
+            let moves_bb = self.get_rook_attacks(from) & !us;
+            for to in moves_bb {
+                bb |= to.bb();
             }
-            from = rooks.pop_lsb();
         }
         bb
     }
@@ -428,28 +830,21 @@ impl Board {
     }
 
     pub fn get_pawn_targets(&self, color: Color) -> Bitboard {
-        let mut pawns = self.pawn_bbs[color as usize];
+        let pawns = self.pawn_bbs[color as usize];
         let us = [self.white_bb, self.black_bb][color as usize];
         let opp = [self.black_bb, self.white_bb][color as usize];
-        let mut from = pawns.pop_lsb();
 
-        let mut bb = 0;
+        let mut bb = Bitboard::EMPTY;
 
-        while from != 65 {
-            let not_blocked = PAWN_AVECS[from as usize] & !us;
-            let (file, _rank) = idx_to_file_rank(from as u8);
-            let not_capture = not_blocked & gen_file_mask(file);
+        for from in pawns {
+            let not_blocked = PAWN_AVECS[from.index()] & !us;
+            let not_capture = not_blocked & from.file().bb();
             let captures = (not_blocked & !not_capture) & opp;
-            let mut valids = not_capture | captures;
+            let valids = not_capture | captures;
 
-            let mut index = valids.pop_lsb();
-            while index != 65 {
-                let to = index;
-                bb |= (1 as u64) << to;
-                index = valids.pop_lsb();
+            for to in valids {
+                bb |= to.bb();
             }
-
-            from = pawns.pop_lsb();
         }
 
         bb
@@ -465,9 +860,47 @@ impl Board {
     }
 }
 
-pub fn pretty_print_index(index: u8) -> String {
-    let (file, rank) = idx_to_file_rank(index);
-    let f = "abcdefgh".as_bytes()[file as usize] as char;
-    let r = char::from_digit(rank as u32 + 1, 10).unwrap();
-    return f.to_string() + &r.to_string();
+// scharnagl numbering, index 518 is the standard starting position
+pub fn chess960_fen(index: u16) -> String {
+    assert!(index < 960, "chess960 index out of range");
+
+    let mut back_rank = [' '; 8];
+    let mut n = index as usize;
+
+    back_rank[(n % 4) * 2 + 1] = 'b';
+    n /= 4;
+    back_rank[(n % 4) * 2] = 'b';
+    n /= 4;
+
+    let mut place_on_nth_empty = |piece: char, nth: usize| {
+        let file = (0..8).filter(|&f| back_rank[f] == ' ').nth(nth).unwrap();
+        back_rank[file] = piece;
+    };
+
+    place_on_nth_empty('q', n % 6);
+    n /= 6;
+
+    let (first, second) = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 1),
+        (1, 2),
+        (1, 3),
+        (2, 2),
+        (2, 3),
+        (3, 3),
+    ][n];
+    // the first knight takes an empty square, so the second index shifts down by one
+    place_on_nth_empty('n', first);
+    place_on_nth_empty('n', second);
+
+    place_on_nth_empty('r', 0);
+    place_on_nth_empty('k', 0);
+    place_on_nth_empty('r', 0);
+
+    let black: String = back_rank.iter().collect();
+    let white = black.to_uppercase();
+    format!("{black}/pppppppp/8/8/8/8/PPPPPPPP/{white} w KQkq - 0 1")
 }
@@ -0,0 +1,43 @@
+use crate::enums::{CastleSide, Color};
+use crate::square::File;
+
+// rights are stored as the file of the rook that can still castle,
+// so the same code handles standard chess and chess960
+#[derive(Clone, Copy, PartialEq)]
+pub struct CastlingRights {
+    rook_files: [[Option<File>; 2]; 2], // [color][side]
+}
+
+impl CastlingRights {
+    pub fn none() -> Self {
+        Self {
+            rook_files: [[None; 2]; 2],
+        }
+    }
+
+    pub fn standard() -> Self {
+        Self {
+            rook_files: [[Some(File::H), Some(File::A)]; 2],
+        }
+    }
+
+    pub fn get(&self, color: Color, side: CastleSide) -> Option<File> {
+        self.rook_files[color as usize][side as usize]
+    }
+
+    pub fn set(&mut self, color: Color, side: CastleSide, rook_file: File) {
+        self.rook_files[color as usize][side as usize] = Some(rook_file);
+    }
+
+    pub fn remove(&mut self, color: Color, side: CastleSide) {
+        self.rook_files[color as usize][side as usize] = None;
+    }
+
+    pub fn remove_color(&mut self, color: Color) {
+        self.rook_files[color as usize] = [None; 2];
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rook_files.iter().flatten().all(|f| f.is_none())
+    }
+}
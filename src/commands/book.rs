@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use crate::commands::parse_number;
+use rustybattler::enums::Color;
+use rustybattler::pgn::parse_pgn;
+use rustybattler::polyglot::builder::{BookBuildOptions, BookBuilder};
+
+const USAGE: &str = "usage: book build <out.bin> <games.pgn>... [--max-ply N] [--min-elo N] [--min-games N] [--only white|black]";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("build") => build(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn build(args: &[String]) -> Result<(), String> {
+    let mut options = BookBuildOptions::default();
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--max-ply" => options.max_ply = parse_number(value()?)?,
+            "--min-elo" => options.min_elo = parse_number(value()?)?,
+            "--min-games" => options.min_games = parse_number(value()?)?,
+            "--only" => {
+                options.only_color = match value()?.as_str() {
+                    "white" => Some(Color::White),
+                    "black" => Some(Color::Black),
+                    other => return Err(format!("unknown colour {other}")),
+                }
+            }
+            _ => paths.push(arg),
+        }
+    }
+
+    let (out, pgns) = paths.split_first().ok_or(USAGE)?;
+    if pgns.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let mut builder = BookBuilder::new(options);
+    for pgn in pgns {
+        let text = std::fs::read_to_string(pgn).map_err(|e| format!("{pgn}: {e}"))?;
+        for game in parse_pgn(&text) {
+            builder.add_game(&game);
+        }
+    }
+
+    let written = builder
+        .write(Path::new(out))
+        .map_err(|e| format!("{out}: {e}"))?;
+    println!(
+        "{} games used, {} skipped, {} entries written to {}",
+        builder.games_used, builder.games_skipped, written, out
+    );
+    Ok(())
+}
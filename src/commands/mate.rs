@@ -0,0 +1,30 @@
+use rustybattler::board::Board;
+use rustybattler::mate::find_mate;
+use rustybattler::notation::move_to_uci;
+
+const USAGE: &str = "usage: mate <N> <fen>";
+
+// stands in for `go mate N` until there is a UCI loop
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (n, fen) = args.split_first().ok_or(USAGE)?;
+    let n: u32 = n.parse().map_err(|_| USAGE.to_string())?;
+    if fen.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let mut board = Board::from_fen(&fen.join(" ")).map_err(|e| e.to_string())?;
+
+    match find_mate(&board, n) {
+        Some(line) => {
+            // replay the line so castling prints the way the board expects
+            let mut pv = Vec::new();
+            for m in &line {
+                pv.push(move_to_uci(&board, *m));
+                board.make_move(*m);
+            }
+            println!("mate in {}: {}", line.len().div_ceil(2), pv.join(" "));
+        }
+        None => println!("no mate within {n} moves"),
+    }
+    Ok(())
+}
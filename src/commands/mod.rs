@@ -0,0 +1,8 @@
+pub mod book;
+pub mod mate;
+pub mod play;
+pub mod play_match;
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("expected a number, got {s}"))
+}
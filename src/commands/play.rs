@@ -0,0 +1,392 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use crate::commands::parse_number;
+use rustybattler::bitboards::Bitboard;
+use rustybattler::board::{Board, STARTING_FEN, chess960_fen};
+use rustybattler::display::DisplayOptions;
+use rustybattler::enums::Color;
+use rustybattler::match_runner::engine::UciEngine;
+use rustybattler::match_runner::game::{GameResult, TimeControl, adjudicate};
+use rustybattler::movegen::move_rep::{Move, MoveExt};
+use rustybattler::notation::{move_to_san, move_to_uci, parse_san, parse_uci};
+use rustybattler::polyglot::polyglot_key;
+
+const USAGE: &str = "usage: play [engine] [--side white|black] [--tc 5+0.1] [--movetime ms] [--fen FEN] [--chess960 [N]] [--flip]";
+const HELP: &str =
+    "moves in SAN (Nf3) or UCI (g1f3). commands: undo, redo, flip, new, fen, help, quit";
+
+// slack on top of the engine's clock before it loses on time
+const ENGINE_MARGIN: Duration = Duration::from_secs(1);
+
+struct Opponent {
+    engine: UciEngine,
+    color: Color,
+}
+
+// the game as a list of positions, so undo is just dropping the last one
+struct Session {
+    boards: Vec<Board>,
+    moves: Vec<Move>,
+    redo: Vec<Move>,
+    time_control: Option<TimeControl>,
+    // clocks keep running through undo, taking moves back doesn't give time back
+    clocks: [Duration; 2],
+    // when the side to move got the move, so time spent on typos counts too
+    turn_started: Instant,
+    flipped: bool,
+    thinking: Option<String>,
+    message: String,
+    over: Option<(GameResult, String)>,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut engine_path = None;
+    let mut side = Color::White;
+    let mut time_control = None;
+    let mut movetime = 1000;
+    let mut fen = STARTING_FEN.to_string();
+    let mut chess960 = false;
+    let mut flip = false;
+
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--side" => {
+                side = match value()?.as_str() {
+                    "white" | "w" => Color::White,
+                    "black" | "b" => Color::Black,
+                    other => return Err(format!("expected white or black, got {other}")),
+                }
+            }
+            "--tc" => {
+                let tc = value()?;
+                time_control =
+                    Some(TimeControl::parse(tc).ok_or(format!("bad time control {tc}"))?);
+            }
+            "--movetime" => movetime = parse_number(value()?)?,
+            "--fen" => fen = value()?.clone(),
+            "--chess960" => {
+                chess960 = true;
+                // the position number is optional, 518 is the standard setup.
+                // anything that isn't a number is left for the engine path
+                if let Some(n) = args.next_if(|a| a.parse::<u16>().is_ok()) {
+                    let index = parse_number(n)?;
+                    if index >= 960 {
+                        return Err(format!("chess960 positions go from 0 to 959, got {index}"));
+                    }
+                    fen = chess960_fen(index);
+                }
+            }
+            "--flip" => flip = true,
+            _ if engine_path.is_none() => engine_path = Some(arg.clone()),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let mut start = Board::from_fen(&fen).map_err(|e| e.to_string())?;
+    let chess960 = chess960 || start.chess960();
+    start.set_chess960(chess960);
+    if let Err(errors) = start.validate() {
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        return Err(errors.join("\n"));
+    }
+
+    let mut opponent = match &engine_path {
+        Some(path) => {
+            let mut engine = UciEngine::start(path).map_err(|e| format!("{path}: {e}"))?;
+            engine
+                .new_game(chess960)
+                .map_err(|e| format!("{path}: {e}"))?;
+            Some(Opponent {
+                engine,
+                color: side.opponent(),
+            })
+        }
+        None => None,
+    };
+
+    let mut session = Session {
+        boards: vec![start],
+        moves: Vec::new(),
+        redo: Vec::new(),
+        time_control,
+        clocks: [time_control.map_or(Duration::ZERO, |tc| tc.base); 2],
+        turn_started: Instant::now(),
+        flipped: flip != (opponent.is_some() && side == Color::Black),
+        thinking: None,
+        message: HELP.to_string(),
+        over: None,
+    };
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        session.check_over();
+        let board = *session.board();
+
+        if session.over.is_none()
+            && let Some(opponent) = &mut opponent
+            && board.side_to_move() == opponent.color
+        {
+            session.draw();
+            session.engine_move(opponent, movetime)?;
+            continue;
+        }
+
+        session.draw();
+        print!("> ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|e| e.to_string())?;
+        let input = line.trim();
+
+        session.message.clear();
+        match input {
+            "" => {}
+            "quit" | "exit" => return Ok(()),
+            "help" => session.message = HELP.to_string(),
+            "flip" => session.flipped = !session.flipped,
+            "fen" => session.message = board.to_fen(),
+            "undo" => session.undo(opponent.as_ref().map(|o| o.color)),
+            "redo" => session.redo(opponent.as_ref().map(|o| o.color)),
+            "new" => {
+                session.boards.truncate(1);
+                session.moves.clear();
+                session.redo.clear();
+                session.clocks = [time_control.map_or(Duration::ZERO, |tc| tc.base); 2];
+                session.turn_started = Instant::now();
+                session.thinking = None;
+                session.over = None;
+                if let Some(opponent) = &mut opponent {
+                    opponent
+                        .engine
+                        .new_game(chess960)
+                        .map_err(|e| format!("{}: {e}", opponent.engine.name))?;
+                }
+            }
+            _ if session.over.is_some() => {
+                session.message = "the game is over, try undo or new".to_string();
+            }
+            _ => match parse_san(&board, input).or_else(|| parse_uci(&board, input)) {
+                Some(m) => {
+                    session.redo.clear();
+                    if session.spend(board.side_to_move(), session.turn_started.elapsed()) {
+                        session.play(m);
+                    }
+                }
+                None => session.message = format!("no legal move {input}"),
+            },
+        }
+    }
+}
+
+impl Session {
+    fn board(&self) -> &Board {
+        self.boards.last().unwrap()
+    }
+
+    fn play(&mut self, m: Move) {
+        let mut next = *self.board();
+        next.make_move(m);
+        self.boards.push(next);
+        self.moves.push(m);
+        self.turn_started = Instant::now();
+    }
+
+    // takes the time off the mover's clock and adds the increment, returns
+    // false and ends the game if the flag fell
+    fn spend(&mut self, color: Color, elapsed: Duration) -> bool {
+        let Some(tc) = self.time_control else {
+            return true;
+        };
+        let clock = &mut self.clocks[color as usize];
+        if elapsed > *clock {
+            *clock = Duration::ZERO;
+            let result = if color == Color::White {
+                GameResult::BlackWins
+            } else {
+                GameResult::WhiteWins
+            };
+            self.over = Some((result, "time forfeit".to_string()));
+            return false;
+        }
+        *clock = *clock - elapsed + tc.increment;
+        true
+    }
+
+    fn check_over(&mut self) {
+        if self.over.is_some() {
+            return;
+        }
+        let history: Vec<u64> = self.boards.iter().map(polyglot_key).collect();
+        if let Some((result, reason)) = adjudicate(self.board(), &history) {
+            self.over = Some((result, reason.to_string()));
+        }
+    }
+
+    // against an engine this goes back to the last position where it was
+    // the player's move, so the engine doesn't just play its reply again
+    fn undo(&mut self, engine: Option<Color>) {
+        if self.moves.is_empty() {
+            self.message = "nothing to undo".to_string();
+            return;
+        }
+        while let Some(m) = self.moves.pop() {
+            self.boards.pop();
+            self.redo.push(m);
+            if engine != Some(self.board().side_to_move()) {
+                break;
+            }
+        }
+        self.over = None;
+        self.turn_started = Instant::now();
+    }
+
+    fn redo(&mut self, engine: Option<Color>) {
+        if self.redo.is_empty() {
+            self.message = "nothing to redo".to_string();
+            return;
+        }
+        while let Some(m) = self.redo.pop() {
+            self.play(m);
+            if engine != Some(self.board().side_to_move()) {
+                break;
+            }
+        }
+    }
+
+    fn engine_move(&mut self, opponent: &mut Opponent, movetime: u64) -> Result<(), String> {
+        let start = &self.boards[0];
+        let start_fen = start.to_fen();
+        let mut position = if start_fen == STARTING_FEN {
+            "position startpos".to_string()
+        } else {
+            format!("position fen {start_fen}")
+        };
+        if !self.moves.is_empty() {
+            position += " moves";
+            for (board, &m) in self.boards.iter().zip(&self.moves) {
+                position += " ";
+                position += &move_to_uci(board, m);
+            }
+        }
+
+        let color = opponent.color;
+        let (go, timeout) = match self.time_control {
+            Some(tc) => (
+                format!(
+                    "go wtime {} btime {} winc {} binc {}",
+                    self.clocks[0].as_millis(),
+                    self.clocks[1].as_millis(),
+                    tc.increment.as_millis(),
+                    tc.increment.as_millis()
+                ),
+                self.clocks[color as usize] + ENGINE_MARGIN,
+            ),
+            None => (
+                format!("go movetime {movetime}"),
+                Duration::from_millis(movetime) + ENGINE_MARGIN,
+            ),
+        };
+
+        let name = opponent.engine.name.clone();
+        let search = match opponent.engine.search(&position, &go, timeout) {
+            Ok(search) => search,
+            Err(e) if e.kind() == io::ErrorKind::TimedOut && self.time_control.is_some() => {
+                // get the late bestmove out of the way before the next search
+                let _ = opponent.engine.send("stop");
+                opponent
+                    .engine
+                    .is_ready()
+                    .map_err(|e| format!("{name}: {e}"))?;
+                self.spend(color, timeout);
+                return Ok(());
+            }
+            Err(e) => return Err(format!("{name}: {e}")),
+        };
+
+        let board = *self.board();
+        let m = parse_uci(&board, &search.bestmove)
+            .ok_or(format!("{name} played an illegal move {}", search.bestmove))?;
+        self.thinking = search.info.map(|info| format!("{name}: {info}"));
+        if self.spend(color, search.elapsed) {
+            self.play(m);
+        }
+        Ok(())
+    }
+
+    fn draw(&self) {
+        let mut out = String::new();
+        if io::stdout().is_terminal() {
+            out += "\x1b[2J\x1b[H";
+        }
+
+        let board = self.board();
+        let options = DisplayOptions {
+            unicode: true,
+            color: true,
+            info: false,
+            flipped: self.flipped,
+            highlight: self
+                .moves
+                .last()
+                .map_or(Bitboard::EMPTY, |m| m.from().bb() | m.to().bb()),
+            ..DisplayOptions::default()
+        };
+        out += &format!("{}\n", board.display(options));
+
+        if let Some((result, reason)) = &self.over {
+            out += &format!("{} ({reason})\n", result.pgn());
+        } else if board.side_to_move() == Color::White {
+            out += "white to move\n";
+        } else {
+            out += "black to move\n";
+        }
+        if self.time_control.is_some() {
+            out += &format!(
+                "white {}  black {}\n",
+                clock(self.clocks[0]),
+                clock(self.clocks[1])
+            );
+        }
+        if !self.moves.is_empty() {
+            out += &self.move_list();
+            out.push('\n');
+        }
+        if let Some(thinking) = &self.thinking {
+            out += thinking;
+            out.push('\n');
+        }
+        if !self.message.is_empty() {
+            out += &self.message;
+            out.push('\n');
+        }
+        print!("{out}");
+    }
+
+    // the last few moves in SAN with their move numbers
+    fn move_list(&self) -> String {
+        let mut tokens = Vec::new();
+        for (i, (board, &m)) in self.boards.iter().zip(&self.moves).enumerate() {
+            let number = board.fullmove_number();
+            if board.side_to_move() == Color::White {
+                tokens.push(format!("{number}."));
+            } else if i == 0 {
+                tokens.push(format!("{number}..."));
+            }
+            tokens.push(move_to_san(board, m));
+        }
+        let skip = tokens.len().saturating_sub(18);
+        tokens[skip..].join(" ")
+    }
+}
+
+fn clock(d: Duration) -> String {
+    let tenths = d.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
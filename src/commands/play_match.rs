@@ -0,0 +1,172 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::commands::parse_number;
+use rustybattler::board::{Board, STARTING_FEN};
+use rustybattler::match_runner::engine::UciEngine;
+use rustybattler::match_runner::game::{GameSettings, TimeControl, play_game};
+use rustybattler::match_runner::stats::{MatchScore, Sprt, SprtStatus};
+use rustybattler::match_runner::{PgnHeaders, load_openings, write_pgn};
+
+const USAGE: &str = "usage: match <engine1> <engine2> [--games N] [--tc 10+0.1] [--openings file] [--pgn out.pgn] [--sprt elo0 elo1] [--alpha A] [--beta B] [--resign-score cp] [--resign-moves N] [--max-moves N] [--margin ms] [--chess960]";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut engine_paths = Vec::new();
+    let mut games = 2;
+    let mut tc = "10+0.1".to_string();
+    let mut openings_path = None;
+    let mut pgn_path = None;
+    let mut sprt = None;
+    let (mut alpha, mut beta) = (0.05, 0.05);
+    let mut settings = GameSettings {
+        time_control: TimeControl::parse(&tc).unwrap(),
+        margin: Duration::from_millis(100),
+        resign_score: 1000,
+        resign_moves: 0,
+        max_moves: 0,
+        chess960: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--games" => games = parse_number(value()?)?,
+            "--tc" => tc = value()?.clone(),
+            "--openings" => openings_path = Some(value()?.clone()),
+            "--pgn" => pgn_path = Some(value()?.clone()),
+            "--sprt" => {
+                let elo0 = parse_number(value()?)?;
+                let elo1 = parse_number(value()?)?;
+                sprt = Some((elo0, elo1));
+            }
+            "--alpha" => alpha = parse_number(value()?)?,
+            "--beta" => beta = parse_number(value()?)?,
+            "--resign-score" => settings.resign_score = parse_number(value()?)?,
+            "--resign-moves" => settings.resign_moves = parse_number(value()?)?,
+            "--max-moves" => settings.max_moves = parse_number(value()?)?,
+            "--margin" => settings.margin = Duration::from_millis(parse_number(value()?)?),
+            "--chess960" => settings.chess960 = true,
+            _ => engine_paths.push(arg.clone()),
+        }
+    }
+
+    let [path1, path2] = engine_paths.as_slice() else {
+        return Err(USAGE.to_string());
+    };
+    settings.time_control = TimeControl::parse(&tc).ok_or(format!("bad time control {tc}"))?;
+    // the SPRT bounds take the log of both, and of one minus both
+    for (name, p) in [("--alpha", alpha), ("--beta", beta)] {
+        if !(p > 0.0 && p < 1.0) {
+            return Err(format!("{name} must be between 0 and 1, got {p}"));
+        }
+    }
+    let sprt = sprt.map(|(elo0, elo1)| Sprt {
+        elo0,
+        elo1,
+        alpha,
+        beta,
+    });
+
+    let openings = match &openings_path {
+        Some(path) => load_openings(Path::new(path)).map_err(|e| format!("{path}: {e}"))?,
+        None => vec![STARTING_FEN.to_string()],
+    };
+    if openings.is_empty() {
+        return Err("no openings found".to_string());
+    }
+
+    let mut pgn_file = match &pgn_path {
+        Some(path) => Some(File::create(path).map_err(|e| format!("{path}: {e}"))?),
+        None => None,
+    };
+
+    let start = |path: &String| UciEngine::start(path).map_err(|e| format!("{path}: {e}"));
+    let mut engines = [start(path1)?, start(path2)?];
+    let names = [engines[0].name.clone(), engines[1].name.clone()];
+    println!("{} vs {}", names[0], names[1]);
+
+    let mut score = MatchScore::default();
+
+    // each opening is played twice with colours swapped, so neither engine
+    // profits from a lopsided opening
+    for round in 0..games {
+        let opening = &openings[(round / 2) as usize % openings.len()];
+        let first_is_white = round % 2 == 0;
+
+        let [first, second] = &mut engines;
+        let (white, black) = if first_is_white {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let (played, error) = play_game([white, black], opening, &settings);
+
+        // a game that never started says nothing about either engine
+        if let Some(result) = played.result {
+            score.record(result, first_is_white);
+        }
+
+        if let Some(file) = &mut pgn_file {
+            let (white, black) = if first_is_white {
+                (&names[0], &names[1])
+            } else {
+                (&names[1], &names[0])
+            };
+            let headers = PgnHeaders {
+                event: "rustybattler match",
+                round: round + 1,
+                white,
+                black,
+                time_control: &tc,
+                // a chess960 opening turns the variant on even without --chess960
+                chess960: settings.chess960
+                    || Board::from_fen(&played.start_fen).is_ok_and(|b| b.chess960()),
+            };
+            file.write_all(write_pgn(&headers, &played).as_bytes())
+                .map_err(|e| format!("writing pgn: {e}"))?;
+        }
+
+        // we can't tell which engine broke, so restart both and carry on
+        if error.is_some() {
+            for (i, path) in [path1, path2].into_iter().enumerate() {
+                engines[i] = start(path)?;
+            }
+        }
+
+        print_status(round + 1, &played.termination, &score, sprt.as_ref());
+
+        if let Some(sprt) = &sprt {
+            match sprt.status(&score) {
+                SprtStatus::Continue => {}
+                SprtStatus::AcceptH0 => {
+                    println!("SPRT: H0 accepted");
+                    break;
+                }
+                SprtStatus::AcceptH1 => {
+                    println!("SPRT: H1 accepted");
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_status(game: u32, termination: &str, score: &MatchScore, sprt: Option<&Sprt>) {
+    let mut line = format!(
+        "game {} ({}): +{} -{} ={}",
+        game, termination, score.wins, score.losses, score.draws
+    );
+    if let Some((elo, margin)) = score.elo() {
+        line += &format!(", elo {elo:.1} +/- {margin:.1}");
+    }
+    if let Some(sprt) = sprt {
+        let (lower, upper) = sprt.bounds();
+        line += &format!(", llr {:.2} ({:.2}, {:.2})", sprt.llr(score), lower, upper);
+    }
+    println!("{line}");
+}
@@ -0,0 +1,190 @@
+use std::fmt;
+
+use crate::bitboards::Bitboard;
+use crate::board::{Board, Piece};
+use crate::enums::{Color, PieceType};
+use crate::square::{File, Rank, Square};
+
+// 256-colour backgrounds: light, dark, then highlighted squares
+const LIGHT: u8 = 180;
+const DARK: u8 = 137;
+const LIGHT_MARKED: u8 = 186;
+const DARK_MARKED: u8 = 143;
+
+#[derive(Clone, Copy)]
+pub struct DisplayOptions {
+    pub unicode: bool,
+    pub color: bool, // ANSI escapes for the squares and pieces
+    pub labels: bool,
+    pub info: bool, // side to move, castling rights and FEN under the board
+    pub flipped: bool,
+    pub highlight: Bitboard, // e.g. the last move. bracketed when there's no colour
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            unicode: false,
+            color: false,
+            labels: true,
+            info: true,
+            flipped: false,
+            highlight: Bitboard::EMPTY,
+        }
+    }
+}
+
+pub struct BoardDisplay<'a> {
+    board: &'a Board,
+    options: DisplayOptions,
+}
+
+// marks the squares of each layer on top of a position (or an empty board),
+// one column per layer, for eyeballing attack maps against each other
+pub struct Overlay<'a> {
+    pub board: Option<&'a Board>,
+    pub layers: Vec<(char, Bitboard)>,
+}
+
+impl Board {
+    pub fn display(&self, options: DisplayOptions) -> BoardDisplay<'_> {
+        BoardDisplay {
+            board: self,
+            options,
+        }
+    }
+}
+
+// the FEN letter, or with unicode the outlined glyph for white and the filled
+// one for black. with colour both sides get the filled glyph, told apart by
+// the foreground
+fn piece_char(p: Piece, unicode: bool, color: bool) -> char {
+    if !unicode {
+        let c = match p.piece {
+            PieceType::None => return '.',
+            PieceType::Pawn => 'p',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Rook => 'r',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k',
+        };
+        return if p.color == Color::White {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        };
+    }
+
+    let glyphs = if p.color == Color::White && !color {
+        ['♙', '♘', '♗', '♖', '♕', '♔']
+    } else {
+        ['♟', '♞', '♝', '♜', '♛', '♚']
+    };
+    match p.piece {
+        PieceType::None if color => ' ',
+        PieceType::None => '·',
+        PieceType::Pawn => glyphs[0],
+        PieceType::Knight => glyphs[1],
+        PieceType::Bishop => glyphs[2],
+        PieceType::Rook => glyphs[3],
+        PieceType::Queen => glyphs[4],
+        PieceType::King => glyphs[5],
+    }
+}
+
+// ranks top to bottom and files left to right as seen by the viewer
+fn orientation(flipped: bool) -> (Vec<Rank>, Vec<File>) {
+    if flipped {
+        (Rank::all().collect(), File::all().rev().collect())
+    } else {
+        (Rank::all().rev().collect(), File::all().collect())
+    }
+}
+
+fn write_files(f: &mut fmt::Formatter, files: &[File], width: usize) -> fmt::Result {
+    write!(f, " ")?;
+    for file in files {
+        write!(f, " {:<w$}", file.to_char(), w = width - 1)?;
+    }
+    writeln!(f)
+}
+
+impl fmt::Display for BoardDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = self.options;
+        let board = self.board;
+        let (ranks, files) = orientation(options.flipped);
+
+        for &rank in &ranks {
+            if options.labels {
+                write!(f, "{rank}")?;
+            }
+            for &file in &files {
+                let sq = Square::new(file, rank);
+                let p = board.piece_at(sq);
+                let c = piece_char(p, options.unicode, options.color);
+                let marked = options.highlight.contains(sq);
+
+                if options.color {
+                    let light = (file.index() + rank.index()) % 2 == 1;
+                    let bg = match (light, marked) {
+                        (true, false) => LIGHT,
+                        (false, false) => DARK,
+                        (true, true) => LIGHT_MARKED,
+                        (false, true) => DARK_MARKED,
+                    };
+                    let fg = if p.color == Color::White { 231 } else { 16 };
+                    write!(f, "\x1b[48;5;{bg}m\x1b[38;5;{fg}m {c} \x1b[0m")?;
+                } else if marked {
+                    write!(f, "[{c}]")?;
+                } else {
+                    write!(f, " {c} ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        if options.labels {
+            write_files(f, &files, 3)?;
+        }
+
+        if options.info {
+            let side = if board.wtm { "white" } else { "black" };
+            writeln!(f, "{side} to move, castling {}", board.castling_field())?;
+            writeln!(f, "{}", board.to_fen())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(DisplayOptions::default()).fmt(f)
+    }
+}
+
+impl fmt::Display for Overlay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ranks, files) = orientation(false);
+        let width = self.layers.len() + 2;
+
+        for &rank in &ranks {
+            write!(f, "{rank}")?;
+            for &file in &files {
+                let sq = Square::new(file, rank);
+                let p = self.board.map_or(Piece::EMPTY, |b| b.piece_at(sq));
+                write!(f, " {}", piece_char(p, false, false))?;
+                for &(mark, bb) in &self.layers {
+                    write!(f, "{}", if bb.contains(sq) { mark } else { ' ' })?;
+                }
+            }
+            writeln!(f)?;
+        }
+        write_files(f, &files, width)?;
+
+        for (mark, bb) in &self.layers {
+            writeln!(f, "{mark}: {} squares", bb.popcount())?;
+        }
+        Ok(())
+    }
+}
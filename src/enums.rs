@@ -1,4 +1,4 @@
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum PieceType {
     None,
     Pawn,
@@ -9,9 +9,40 @@ pub enum PieceType {
     King,
 }
 
-#[derive(Copy, Clone, PartialEq)]
+impl PieceType {
+    // reads a piece back out of the low 3 bits of a packed value
+    pub fn from_bits(bits: u32) -> Self {
+        match bits & 0b111 {
+            1 => PieceType::Pawn,
+            2 => PieceType::Knight,
+            3 => PieceType::Bishop,
+            4 => PieceType::Rook,
+            5 => PieceType::Queen,
+            6 => PieceType::King,
+            _ => PieceType::None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Color {
     None = 2,
     White = 0,
     Black = 1,
 }
+
+impl Color {
+    pub fn opponent(&self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+            Color::None => Color::None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CastleSide {
+    King = 0,
+    Queen = 1,
+}
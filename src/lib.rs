@@ -0,0 +1,40 @@
+//! Chess engine library behind the rustybattler binary.
+//!
+//! The root re-exports cover everyday use: set up a `Board` from FEN,
+//! generate and play moves, and read or write them in SAN/UCI. The modules
+//! stay public for the lower-level pieces (bitboards, polyglot books, PGN,
+//! board rendering). `Board` keeps its representation private, so go
+//! through its methods rather than the mailbox or bitboards.
+//!
+//! Hidden modules are there for the binary, tests and fuzzers and may
+//! change in any release.
+
+pub mod bitboards;
+pub mod board;
+pub mod castling;
+pub mod display;
+pub mod enums;
+// the engine-vs-engine runner behind the match and play commands
+#[doc(hidden)]
+pub mod match_runner;
+pub mod mate;
+pub mod movegen;
+pub mod notation;
+pub mod pgn;
+pub mod polyglot;
+pub mod square;
+pub mod svg;
+pub mod syzygy;
+pub mod validate;
+
+pub use board::{Board, FenError, STARTING_FEN, chess960_fen};
+pub use castling::CastlingRights;
+pub use display::{DisplayOptions, Overlay};
+pub use enums::{CastleSide, Color, PieceType};
+pub use mate::find_mate;
+pub use movegen::legals::gen_legal_moves;
+pub use movegen::move_rep::{Move, MoveExt, MoveList};
+pub use notation::{move_to_san, move_to_uci, parse_san, parse_uci};
+pub use square::{File, Rank, Square};
+pub use svg::SvgOptions;
+pub use validate::PositionError;
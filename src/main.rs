@@ -1,13 +1,25 @@
-use crate::{bitboards::BitboardExt, board::Board, enums::Color};
+use rustybattler::{Board, Color};
 
-mod bitboards;
-mod board;
-mod enums;
-mod movegen;
+mod commands;
 
 fn main() {
-    let mut game = Board::get_empty_board();
-    game.init();
-    game.remove_piece(25);
-    game.white_bb.print();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("book") => commands::book::run(&args[1..]),
+        Some("mate") => commands::mate::run(&args[1..]),
+        Some("match") => commands::play_match::run(&args[1..]),
+        Some("play") => commands::play::run(&args[1..]),
+        _ => {
+            let mut game = Board::get_empty_board();
+            game.init();
+            println!("\n{}", game.get_color_squares(Color::White));
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,158 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// a UCI engine running as a child process. stdout is read on its own thread
+// so a hanging engine can be timed out instead of blocking the match
+pub struct UciEngine {
+    pub name: String,
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+pub struct SearchResult {
+    pub bestmove: String,
+    pub score: Option<i32>, // centipawns from the engine's side, mates as +-30000
+    pub info: Option<String>, // the last info line with a pv, without the "info"
+    pub elapsed: Duration,
+}
+
+impl UciEngine {
+    pub fn start(path: &str) -> io::Result<Self> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = Self {
+            name: path.to_string(),
+            child,
+            stdin,
+            lines,
+        };
+
+        engine.send("uci")?;
+        let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+        loop {
+            let line = engine.read_line(deadline)?;
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = name.trim().to_string();
+            } else if line.trim() == "uciok" {
+                break;
+            }
+        }
+
+        Ok(engine)
+    }
+
+    pub fn send(&mut self, command: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{command}")?;
+        self.stdin.flush()
+    }
+
+    fn read_line(&mut self, deadline: Instant) -> io::Result<String> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match self.lines.recv_timeout(timeout) {
+            Ok(line) => Ok(line),
+            Err(RecvTimeoutError::Timeout) => Err(io::ErrorKind::TimedOut.into()),
+            Err(RecvTimeoutError::Disconnected) => Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+
+    pub fn is_ready(&mut self) -> io::Result<()> {
+        self.send("isready")?;
+        let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+        while self.read_line(deadline)?.trim() != "readyok" {}
+        Ok(())
+    }
+
+    pub fn new_game(&mut self, chess960: bool) -> io::Result<()> {
+        if chess960 {
+            self.send("setoption name UCI_Chess960 value true")?;
+        }
+        self.send("ucinewgame")?;
+        self.is_ready()
+    }
+
+    // sends the position and go command, then waits for bestmove until timeout
+    pub fn search(
+        &mut self,
+        position: &str,
+        go: &str,
+        timeout: Duration,
+    ) -> io::Result<SearchResult> {
+        self.send(position)?;
+        self.send(go)?;
+
+        let start = Instant::now();
+        let deadline = start + timeout;
+        let mut score = None;
+        let mut info = None;
+
+        loop {
+            let line = self.read_line(deadline)?;
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("info") => {
+                    if line.contains(" pv ") {
+                        info = Some(line.trim_start()["info".len()..].trim().to_string());
+                    }
+                    while let Some(token) = tokens.next() {
+                        if token != "score" {
+                            continue;
+                        }
+                        score = match (tokens.next(), tokens.next().and_then(|v| v.parse().ok())) {
+                            (Some("cp"), Some(cp)) => Some(cp),
+                            (Some("mate"), Some(n)) if n > 0 => Some(30000),
+                            (Some("mate"), Some(_)) => Some(-30000),
+                            _ => score,
+                        };
+                    }
+                }
+                Some("bestmove") => {
+                    let bestmove = tokens.next().unwrap_or("").to_string();
+                    return Ok(SearchResult {
+                        bestmove,
+                        score,
+                        info,
+                        elapsed: start.elapsed(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        for _ in 0..20 {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
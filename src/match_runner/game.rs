@@ -0,0 +1,222 @@
+use std::io;
+use std::time::Duration;
+
+use crate::board::{Board, STARTING_FEN};
+use crate::enums::Color;
+use crate::match_runner::engine::UciEngine;
+use crate::movegen::legals::gen_legal_moves;
+use crate::notation::{move_to_san, move_to_uci, parse_uci};
+use crate::polyglot::polyglot_key;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+impl GameResult {
+    pub fn pgn(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+        }
+    }
+
+    fn win_for(color: Color) -> Self {
+        if color == Color::White {
+            GameResult::WhiteWins
+        } else {
+            GameResult::BlackWins
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+}
+
+impl TimeControl {
+    // "60+0.6" is 60 seconds plus 0.6 per move, a bare "60" has no increment
+    pub fn parse(s: &str) -> Option<Self> {
+        let (base, increment) = s.split_once('+').unwrap_or((s, "0"));
+        Some(Self {
+            base: Duration::try_from_secs_f64(base.parse().ok()?).ok()?,
+            increment: Duration::try_from_secs_f64(increment.parse().ok()?).ok()?,
+        })
+    }
+}
+
+pub struct GameSettings {
+    pub time_control: TimeControl,
+    pub margin: Duration, // slack on top of the clock before a loss on time
+    pub resign_score: i32,
+    pub resign_moves: u32, // consecutive moves both engines must agree, 0 disables
+    pub max_moves: u32,    // adjudicated a draw after this many moves, 0 disables
+    pub chess960: bool,
+}
+
+pub struct PlayedGame {
+    pub start_fen: String,
+    pub sans: Vec<String>,
+    pub result: Option<GameResult>, // None when the game never got going
+    pub termination: String,
+}
+
+// engines[0] plays white. an engine that crashes, stalls or sends an illegal
+// move loses the game, and the error is passed back so the caller can restart it
+pub fn play_game(
+    mut engines: [&mut UciEngine; 2],
+    start_fen: &str,
+    settings: &GameSettings,
+) -> (PlayedGame, Option<io::Error>) {
+    let mut board = Board::get_empty_board();
+    board.chess960 = settings.chess960;
+    board.load_fen(start_fen);
+    // EPD openings have no move counters, so write the FEN out in full
+    let start_fen = board.to_fen();
+
+    let mut game = PlayedGame {
+        start_fen: start_fen.clone(),
+        sans: Vec::new(),
+        result: None,
+        termination: String::new(),
+    };
+
+    for engine in engines.iter_mut() {
+        if let Err(e) = engine.new_game(board.chess960) {
+            // can't tell whose fault it is, so the game doesn't count
+            game.termination = "engine failed to start a game".to_string();
+            return (game, Some(e));
+        }
+    }
+
+    let mut clocks = [settings.time_control.base; 2];
+    let mut uci_moves: Vec<String> = Vec::new();
+    let mut history = vec![polyglot_key(&board)];
+    let mut losing_streak = [0; 2]; // moves in a row each side has reported a resign score
+    let mut plies = 0;
+
+    let position_prefix = if start_fen == STARTING_FEN {
+        "position startpos".to_string()
+    } else {
+        format!("position fen {start_fen}")
+    };
+
+    loop {
+        let us = board.side_to_move();
+
+        if let Some((result, reason)) = adjudicate(&board, &history) {
+            game.result = Some(result);
+            game.termination = reason.to_string();
+            return (game, None);
+        }
+        if settings.max_moves > 0 && plies >= settings.max_moves * 2 {
+            game.result = Some(GameResult::Draw);
+            game.termination = "move limit".to_string();
+            return (game, None);
+        }
+
+        let position = if uci_moves.is_empty() {
+            position_prefix.clone()
+        } else {
+            format!("{} moves {}", position_prefix, uci_moves.join(" "))
+        };
+        let tc = settings.time_control;
+        let go = format!(
+            "go wtime {} btime {} winc {} binc {}",
+            clocks[0].as_millis(),
+            clocks[1].as_millis(),
+            tc.increment.as_millis(),
+            tc.increment.as_millis()
+        );
+
+        let engine = &mut engines[us as usize];
+        let timeout = clocks[us as usize] + settings.margin;
+        let search = match engine.search(&position, &go, timeout) {
+            Ok(search) => search,
+            Err(e) => {
+                game.result = Some(GameResult::win_for(us.opponent()));
+                game.termination = if e.kind() == io::ErrorKind::TimedOut {
+                    "time forfeit".to_string()
+                } else {
+                    "engine crashed".to_string()
+                };
+                return (game, Some(e));
+            }
+        };
+
+        if search.elapsed > timeout {
+            game.result = Some(GameResult::win_for(us.opponent()));
+            game.termination = "time forfeit".to_string();
+            return (game, None);
+        }
+        clocks[us as usize] = clocks[us as usize].saturating_sub(search.elapsed) + tc.increment;
+
+        let Some(m) = parse_uci(&board, &search.bestmove) else {
+            game.result = Some(GameResult::win_for(us.opponent()));
+            game.termination = format!("illegal move {}", search.bestmove);
+            return (game, None);
+        };
+
+        // resign once both engines have agreed on the outcome for long enough
+        let score = search.score.unwrap_or(0);
+        losing_streak[us as usize] = if score <= -settings.resign_score {
+            losing_streak[us as usize] + 1
+        } else {
+            0
+        };
+        losing_streak[us.opponent() as usize] = if score >= settings.resign_score {
+            losing_streak[us.opponent() as usize] + 1
+        } else {
+            0
+        };
+
+        game.sans.push(move_to_san(&board, m));
+        uci_moves.push(move_to_uci(&board, m));
+        board.make_move(m);
+        history.push(polyglot_key(&board));
+        plies += 1;
+
+        if settings.resign_moves > 0 {
+            for color in [Color::White, Color::Black] {
+                if losing_streak[color as usize] >= settings.resign_moves * 2 {
+                    game.result = Some(GameResult::win_for(color.opponent()));
+                    game.termination = "adjudication".to_string();
+                    return (game, None);
+                }
+            }
+        }
+    }
+}
+
+// history holds the polyglot key of every position so far, the current one last
+pub fn adjudicate(board: &Board, history: &[u64]) -> Option<(GameResult, &'static str)> {
+    let us = board.side_to_move();
+
+    if gen_legal_moves(board).is_empty() {
+        return Some(if board.in_check(us) {
+            (GameResult::win_for(us.opponent()), "checkmate")
+        } else {
+            (GameResult::Draw, "stalemate")
+        });
+    }
+    if board.reversables >= 100 {
+        return Some((GameResult::Draw, "fifty-move rule"));
+    }
+    if board.is_insufficient_material() {
+        return Some((GameResult::Draw, "insufficient material"));
+    }
+
+    // repeats can only happen since the last irreversible move
+    let current = *history.last().unwrap();
+    let since_reset = &history[history.len().saturating_sub(board.reversables as usize + 1)..];
+    if since_reset.iter().filter(|&&key| key == current).count() >= 3 {
+        return Some((GameResult::Draw, "threefold repetition"));
+    }
+
+    None
+}
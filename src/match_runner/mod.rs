@@ -0,0 +1,125 @@
+pub mod engine;
+pub mod game;
+pub mod stats;
+
+use std::io;
+use std::path::Path;
+
+use crate::board::{Board, FenError, STARTING_FEN};
+use crate::match_runner::game::PlayedGame;
+use crate::notation::parse_san;
+use crate::pgn::parse_pgn;
+
+fn bad_fen(fen: &str, e: FenError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{fen}: {e}"))
+}
+
+// one FEN or EPD per line, or a PGN whose games are replayed to their last move
+pub fn load_openings(path: &Path) -> io::Result<Vec<String>> {
+    let text = std::fs::read_to_string(path)?;
+
+    if path.extension().is_some_and(|e| e == "pgn") {
+        let mut openings = Vec::new();
+        for game in parse_pgn(&text) {
+            let fen = game.header("FEN").unwrap_or(STARTING_FEN);
+            let mut board = Board::from_fen(fen).map_err(|e| bad_fen(fen, e))?;
+            for san in &game.moves {
+                let Some(m) = parse_san(&board, san) else {
+                    break;
+                };
+                board.make_move(m);
+            }
+            openings.push(board.to_fen());
+        }
+        return Ok(openings);
+    }
+
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            // EPD has only four fields followed by opcodes like "bm Nf3;"
+            let fields: Vec<&str> = l.split_whitespace().collect();
+            let fen =
+                if fields.len() > 6 || fields.get(4).is_some_and(|f| f.parse::<u32>().is_err()) {
+                    fields[..4].join(" ")
+                } else {
+                    l.to_string()
+                };
+            Board::from_fen(&fen).map_err(|e| bad_fen(&fen, e))?;
+            Ok(fen)
+        })
+        .collect()
+}
+
+pub struct PgnHeaders<'a> {
+    pub event: &'a str,
+    pub round: u32,
+    pub white: &'a str,
+    pub black: &'a str,
+    pub time_control: &'a str,
+    pub chess960: bool,
+}
+
+pub fn write_pgn(headers: &PgnHeaders, game: &PlayedGame) -> String {
+    let mut pgn = String::new();
+    let mut tag = |name: &str, value: &str| {
+        pgn += &format!("[{} \"{}\"]\n", name, value.replace('"', "\\\""));
+    };
+
+    tag("Event", headers.event);
+    tag("Site", "?");
+    tag("Round", &headers.round.to_string());
+    tag("White", headers.white);
+    tag("Black", headers.black);
+    // a game that never started has no result
+    let result = game.result.map_or("*", |r| r.pgn());
+    tag("Result", result);
+    if headers.chess960 {
+        tag("Variant", "Chess960");
+    }
+    if game.start_fen != STARTING_FEN {
+        tag("SetUp", "1");
+        tag("FEN", &game.start_fen);
+    }
+    tag("TimeControl", headers.time_control);
+    tag("Termination", &game.termination);
+    pgn.push('\n');
+
+    // move numbers continue from the opening position
+    let fields: Vec<&str> = game.start_fen.split(' ').collect();
+    let mut number: u32 = fields.get(5).and_then(|n| n.parse().ok()).unwrap_or(1);
+    let mut white_to_move = fields.get(1) != Some(&"b");
+
+    let mut tokens = Vec::new();
+    for (i, san) in game.sans.iter().enumerate() {
+        if white_to_move {
+            tokens.push(format!("{number}."));
+        } else if i == 0 {
+            tokens.push(format!("{number}..."));
+        }
+        tokens.push(san.clone());
+        if !white_to_move {
+            number += 1;
+        }
+        white_to_move = !white_to_move;
+    }
+    tokens.push(result.to_string());
+
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + 1 + token.len() > 80 {
+            pgn += &line;
+            pgn.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line += &token;
+    }
+    pgn += &line;
+    pgn += "\n\n";
+
+    pgn
+}
@@ -0,0 +1,110 @@
+use crate::match_runner::game::GameResult;
+
+// results are from the first engine's point of view
+#[derive(Clone, Copy, Default)]
+pub struct MatchScore {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl MatchScore {
+    pub fn record(&mut self, result: GameResult, first_is_white: bool) {
+        match result {
+            GameResult::Draw => self.draws += 1,
+            GameResult::WhiteWins if first_is_white => self.wins += 1,
+            GameResult::BlackWins if !first_is_white => self.wins += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+
+    pub fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64
+    }
+
+    // per-game variance of the score
+    fn variance(&self) -> f64 {
+        let n = self.games() as f64;
+        let s = self.score();
+        (self.wins as f64 * (1.0 - s).powi(2)
+            + self.draws as f64 * (0.5 - s).powi(2)
+            + self.losses as f64 * s.powi(2))
+            / n
+    }
+
+    // elo difference with the half-width of its 95% confidence interval.
+    // None with no games or a clean sweep, where the elo is infinite
+    pub fn elo(&self) -> Option<(f64, f64)> {
+        if self.games() == 0 || self.wins + self.draws == 0 || self.losses + self.draws == 0 {
+            return None;
+        }
+
+        let s = self.score();
+        let stderr = (self.variance() / self.games() as f64).sqrt();
+        let low = score_to_elo((s - 1.96 * stderr).max(1e-6));
+        let high = score_to_elo((s + 1.96 * stderr).min(1.0 - 1e-6));
+        Some((score_to_elo(s), (high - low) / 2.0))
+    }
+}
+
+pub fn score_to_elo(score: f64) -> f64 {
+    400.0 * (score / (1.0 - score)).log10()
+}
+
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SprtStatus {
+    Continue,
+    AcceptH0, // the patch is no better than elo0
+    AcceptH1, // the patch gains at least elo1
+}
+
+// sequential probability ratio test between elo0 and elo1, using the
+// normal approximation of the log-likelihood ratio
+#[derive(Clone, Copy)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Sprt {
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    pub fn llr(&self, score: &MatchScore) -> f64 {
+        let variance = score.variance();
+        if score.games() == 0 || variance == 0.0 {
+            return 0.0;
+        }
+
+        let s0 = elo_to_score(self.elo0);
+        let s1 = elo_to_score(self.elo1);
+        let n = score.games() as f64;
+        n * (s1 - s0) * (2.0 * score.score() - s0 - s1) / (2.0 * variance)
+    }
+
+    pub fn status(&self, score: &MatchScore) -> SprtStatus {
+        let llr = self.llr(score);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            SprtStatus::AcceptH1
+        } else if llr <= lower {
+            SprtStatus::AcceptH0
+        } else {
+            SprtStatus::Continue
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::board::Board;
+use crate::movegen::legals::gen_legal_moves;
+use crate::movegen::move_rep::Move;
+
+// exhaustive search, so a returned line is a proven mate. depths are tried in
+// increasing order, so it's also the shortest one. n counts the attacker's moves
+pub fn find_mate(board: &Board, max_moves: u32) -> Option<Vec<Move>> {
+    let n = (1..=max_moves).find(|&n| mating_move(board, n).is_some())?;
+    Some(mate_line(board, n))
+}
+
+// an attacker move that forces mate within n moves
+fn mating_move(board: &Board, n: u32) -> Option<Move> {
+    let them = board.side_to_move().opponent();
+
+    let mut candidates: Vec<(Move, Board, bool)> = gen_legal_moves(board)
+        .into_iter()
+        .map(|m| {
+            let mut after = *board;
+            after.make_move(m);
+            let gives_check = after.in_check(them);
+            (m, after, gives_check)
+        })
+        .collect();
+
+    // only a check can mate on the last move, and checks are the likeliest
+    // to force things earlier too
+    if n == 1 {
+        candidates.retain(|&(_, _, gives_check)| gives_check);
+    }
+    candidates.sort_by_key(|&(_, _, gives_check)| !gives_check);
+
+    candidates
+        .into_iter()
+        .find(|(_, after, _)| defender_loses(after, n - 1))
+        .map(|(m, _, _)| m)
+}
+
+// defender to move: true if every reply still gets mated within n attacker moves
+fn defender_loses(board: &Board, n: u32) -> bool {
+    let replies = gen_legal_moves(board);
+    if replies.is_empty() {
+        // no moves and not in check is stalemate, which doesn't count
+        return board.in_check(board.side_to_move());
+    }
+    if n == 0 {
+        return false;
+    }
+
+    replies.into_iter().all(|r| {
+        let mut after = *board;
+        after.make_move(r);
+        mating_move(&after, n).is_some()
+    })
+}
+
+// the defender's moves in the line are the ones that hold out the longest
+fn mate_line(board: &Board, n: u32) -> Vec<Move> {
+    let m = mating_move(board, n).unwrap();
+    let mut after = *board;
+    after.make_move(m);
+
+    let longest_defence = gen_legal_moves(&after)
+        .into_iter()
+        .map(|r| {
+            let mut next = after;
+            next.make_move(r);
+            let k = (1..n).find(|&k| mating_move(&next, k).is_some()).unwrap();
+            (k, r, next)
+        })
+        .max_by_key(|&(k, _, _)| k);
+
+    let mut line = vec![m];
+    if let Some((k, r, next)) = longest_defence {
+        line.push(r);
+        line.extend(mate_line(&next, k));
+    }
+    line
+}
@@ -3,403 +3,336 @@ use crate::bitboards::Bitboard;
 pub type AttackVector = [Bitboard; 64];
 
 pub static HORSEY_AVECS: AttackVector = [
-    132096,
-    329728,
-    659712,
-    1319424,
-    2638848,
-    5277696,
-    10489856,
-    4202496,
-    33816580,
-    84410376,
-    168886289,
-    337772578,
-    675545156,
-    1351090312,
-    2685403152,
-    1075839008,
-    8657044482,
-    21609056261,
-    43234889994,
-    86469779988,
-    172939559976,
-    345879119952,
-    687463207072,
-    275414786112,
-    2216203387392,
-    5531918402816,
-    11068131838464,
-    22136263676928,
-    44272527353856,
-    88545054707712,
-    175990581010432,
-    70506185244672,
-    567348067172352,
-    1416171111120896,
-    2833441750646784,
-    5666883501293568,
-    11333767002587136,
-    22667534005174272,
-    45053588738670592,
-    18049583422636032,
-    145241105196122112,
-    362539804446949376,
-    725361088165576704,
-    1450722176331153408,
-    2901444352662306816,
-    5802888705324613632,
-    11533718717099671552,
-    4620693356194824192,
-    288234782788157440,
-    576469569871282176,
-    1224997833292120064,
-    2449995666584240128,
-    4899991333168480256,
-    9799982666336960512,
-    1152939783987658752,
-    2305878468463689728,
-    1128098930098176,
-    2257297371824128,
-    4796069720358912,
-    9592139440717824,
-    19184278881435648,
-    38368557762871296,
-    4679521487814656,
-    9077567998918656,
+    Bitboard(132096),
+    Bitboard(329728),
+    Bitboard(659712),
+    Bitboard(1319424),
+    Bitboard(2638848),
+    Bitboard(5277696),
+    Bitboard(10489856),
+    Bitboard(4202496),
+    Bitboard(33816580),
+    Bitboard(84410376),
+    Bitboard(168886289),
+    Bitboard(337772578),
+    Bitboard(675545156),
+    Bitboard(1351090312),
+    Bitboard(2685403152),
+    Bitboard(1075839008),
+    Bitboard(8657044482),
+    Bitboard(21609056261),
+    Bitboard(43234889994),
+    Bitboard(86469779988),
+    Bitboard(172939559976),
+    Bitboard(345879119952),
+    Bitboard(687463207072),
+    Bitboard(275414786112),
+    Bitboard(2216203387392),
+    Bitboard(5531918402816),
+    Bitboard(11068131838464),
+    Bitboard(22136263676928),
+    Bitboard(44272527353856),
+    Bitboard(88545054707712),
+    Bitboard(175990581010432),
+    Bitboard(70506185244672),
+    Bitboard(567348067172352),
+    Bitboard(1416171111120896),
+    Bitboard(2833441750646784),
+    Bitboard(5666883501293568),
+    Bitboard(11333767002587136),
+    Bitboard(22667534005174272),
+    Bitboard(45053588738670592),
+    Bitboard(18049583422636032),
+    Bitboard(145241105196122112),
+    Bitboard(362539804446949376),
+    Bitboard(725361088165576704),
+    Bitboard(1450722176331153408),
+    Bitboard(2901444352662306816),
+    Bitboard(5802888705324613632),
+    Bitboard(11533718717099671552),
+    Bitboard(4620693356194824192),
+    Bitboard(288234782788157440),
+    Bitboard(576469569871282176),
+    Bitboard(1224997833292120064),
+    Bitboard(2449995666584240128),
+    Bitboard(4899991333168480256),
+    Bitboard(9799982666336960512),
+    Bitboard(1152939783987658752),
+    Bitboard(2305878468463689728),
+    Bitboard(1128098930098176),
+    Bitboard(2257297371824128),
+    Bitboard(4796069720358912),
+    Bitboard(9592139440717824),
+    Bitboard(19184278881435648),
+    Bitboard(38368557762871296),
+    Bitboard(4679521487814656),
+    Bitboard(9077567998918656),
 ];
 
 pub static CROWNIES_AVECS: AttackVector = [
-    770,
-    1797,
-    3594,
-    7188,
-    14376,
-    28752,
-    57504,
-    49216,
-    197123,
-    460039,
-    920078,
-    1840156,
-    3680312,
-    7360624,
-    14721248,
-    12599488,
-    50463488,
-    117769984,
-    235539968,
-    471079936,
-    942159872,
-    1884319744,
-    3768639488,
-    3225468928,
-    12918652928,
-    30149115904,
-    60298231808,
-    120596463616,
-    241192927232,
-    482385854464,
-    964771708928,
-    825720045568,
-    3307175149568,
-    7718173671424,
-    15436347342848,
-    30872694685696,
-    61745389371392,
-    123490778742784,
-    246981557485568,
-    211384331665408,
-    846636838289408,
-    1975852459884544,
-    3951704919769088,
-    7903409839538176,
-    15806819679076352,
-    31613639358152704,
-    63227278716305408,
-    54114388906344448,
-    216739030602088448,
-    505818229730443264,
-    1011636459460886528,
-    2023272918921773056,
-    4046545837843546112,
-    8093091675687092224,
-    16186183351374184448,
-    13853283560024178688,
-    144959613005987840,
-    362258295026614272,
-    724516590053228544,
-    1449033180106457088,
-    2898066360212914176,
-    5796132720425828352,
-    11592265440851656704,
-    4665729213955833856,
+    Bitboard(770),
+    Bitboard(1797),
+    Bitboard(3594),
+    Bitboard(7188),
+    Bitboard(14376),
+    Bitboard(28752),
+    Bitboard(57504),
+    Bitboard(49216),
+    Bitboard(197123),
+    Bitboard(460039),
+    Bitboard(920078),
+    Bitboard(1840156),
+    Bitboard(3680312),
+    Bitboard(7360624),
+    Bitboard(14721248),
+    Bitboard(12599488),
+    Bitboard(50463488),
+    Bitboard(117769984),
+    Bitboard(235539968),
+    Bitboard(471079936),
+    Bitboard(942159872),
+    Bitboard(1884319744),
+    Bitboard(3768639488),
+    Bitboard(3225468928),
+    Bitboard(12918652928),
+    Bitboard(30149115904),
+    Bitboard(60298231808),
+    Bitboard(120596463616),
+    Bitboard(241192927232),
+    Bitboard(482385854464),
+    Bitboard(964771708928),
+    Bitboard(825720045568),
+    Bitboard(3307175149568),
+    Bitboard(7718173671424),
+    Bitboard(15436347342848),
+    Bitboard(30872694685696),
+    Bitboard(61745389371392),
+    Bitboard(123490778742784),
+    Bitboard(246981557485568),
+    Bitboard(211384331665408),
+    Bitboard(846636838289408),
+    Bitboard(1975852459884544),
+    Bitboard(3951704919769088),
+    Bitboard(7903409839538176),
+    Bitboard(15806819679076352),
+    Bitboard(31613639358152704),
+    Bitboard(63227278716305408),
+    Bitboard(54114388906344448),
+    Bitboard(216739030602088448),
+    Bitboard(505818229730443264),
+    Bitboard(1011636459460886528),
+    Bitboard(2023272918921773056),
+    Bitboard(4046545837843546112),
+    Bitboard(8093091675687092224),
+    Bitboard(16186183351374184448),
+    Bitboard(13853283560024178688),
+    Bitboard(144959613005987840),
+    Bitboard(362258295026614272),
+    Bitboard(724516590053228544),
+    Bitboard(1449033180106457088),
+    Bitboard(2898066360212914176),
+    Bitboard(5796132720425828352),
+    Bitboard(11592265440851656704),
+    Bitboard(4665729213955833856),
 ];
 
 pub static PAWN_AVECS: AttackVector = [
-    512,
-    1280,
-    2560,
-    5120,
-    10240,
-    20480,
-    40960,
-    16384,
-    131072,
-    327680,
-    655360,
-    1310720,
-    2621440,
-    5242880,
-    10485760,
-    4194304,
-    33554432,
-    83886080,
-    167772160,
-    335544320,
-    671088640,
-    1342177280,
-    2684354560,
-    1073741824,
-    8589934592,
-    21474836480,
-    42949672960,
-    85899345920,
-    171798691840,
-    343597383680,
-    687194767360,
-    274877906944,
-    2199023255552,
-    5497558138880,
-    10995116277760,
-    21990232555520,
-    43980465111040,
-    87960930222080,
-    175921860444160,
-    70368744177664,
-    562949953421312,
-    1407374883553280,
-    2814749767106560,
-    5629499534213120,
-    11258999068426240,
-    22517998136852480,
-    45035996273704960,
-    18014398509481984,
-    144115188075855872,
-    360287970189639680,
-    720575940379279360,
-    1441151880758558720,
-    2882303761517117440,
-    5764607523034234880,
-    11529215046068469760,
-    4611686018427387904,
-    0,
-    0,
-    0,
-    0,
-    0,
-    0,
-    0,
-    0,
+    Bitboard(512),
+    Bitboard(1280),
+    Bitboard(2560),
+    Bitboard(5120),
+    Bitboard(10240),
+    Bitboard(20480),
+    Bitboard(40960),
+    Bitboard(16384),
+    Bitboard(131072),
+    Bitboard(327680),
+    Bitboard(655360),
+    Bitboard(1310720),
+    Bitboard(2621440),
+    Bitboard(5242880),
+    Bitboard(10485760),
+    Bitboard(4194304),
+    Bitboard(33554432),
+    Bitboard(83886080),
+    Bitboard(167772160),
+    Bitboard(335544320),
+    Bitboard(671088640),
+    Bitboard(1342177280),
+    Bitboard(2684354560),
+    Bitboard(1073741824),
+    Bitboard(8589934592),
+    Bitboard(21474836480),
+    Bitboard(42949672960),
+    Bitboard(85899345920),
+    Bitboard(171798691840),
+    Bitboard(343597383680),
+    Bitboard(687194767360),
+    Bitboard(274877906944),
+    Bitboard(2199023255552),
+    Bitboard(5497558138880),
+    Bitboard(10995116277760),
+    Bitboard(21990232555520),
+    Bitboard(43980465111040),
+    Bitboard(87960930222080),
+    Bitboard(175921860444160),
+    Bitboard(70368744177664),
+    Bitboard(562949953421312),
+    Bitboard(1407374883553280),
+    Bitboard(2814749767106560),
+    Bitboard(5629499534213120),
+    Bitboard(11258999068426240),
+    Bitboard(22517998136852480),
+    Bitboard(45035996273704960),
+    Bitboard(18014398509481984),
+    Bitboard(144115188075855872),
+    Bitboard(360287970189639680),
+    Bitboard(720575940379279360),
+    Bitboard(1441151880758558720),
+    Bitboard(2882303761517117440),
+    Bitboard(5764607523034234880),
+    Bitboard(11529215046068469760),
+    Bitboard(4611686018427387904),
+    Bitboard(0),
+    Bitboard(0),
+    Bitboard(0),
+    Bitboard(0),
+    Bitboard(0),
+    Bitboard(0),
+    Bitboard(0),
+    Bitboard(0),
 ];
 
 pub static ROOK_AVECS: AttackVector = [
-    72340172838076926,
-    144680345676153597,
-    289360691352306939,
-    578721382704613623,
-    1157442765409226991,
-    2314885530818453727,
-    4629771061636907199,
-    9259542123273814143,
-    72340172838141441,
-    144680345676217602,
-    289360691352369924,
-    578721382704674568,
-    1157442765409283856,
-    2314885530818502432,
-    4629771061636939584,
-    9259542123273813888,
-    72340172854657281,
-    144680345692602882,
-    289360691368494084,
-    578721382720276488,
-    1157442765423841296,
-    2314885530830970912,
-    4629771061645230144,
-    9259542123273748608,
-    72340177082712321,
-    144680349887234562,
-    289360695496279044,
-    578721386714368008,
-    1157442769150545936,
-    2314885534022901792,
-    4629771063767613504,
-    9259542123257036928,
-    72341259464802561,
-    144681423712944642,
-    289361752209228804,
-    578722409201797128,
-    1157443723186933776,
-    2314886351157207072,
-    4629771607097753664,
-    9259542118978846848,
-    72618349279904001,
-    144956323094725122,
-    289632270724367364,
-    578984165983651848,
-    1157687956502220816,
-    2315095537539358752,
-    4629910699613634624,
-    9259541023762186368,
-    143553341945872641,
-    215330564830528002,
-    358885010599838724,
-    645993902138460168,
-    1220211685215703056,
-    2368647251370188832,
-    4665518383679160384,
-    9259260648297103488,
-    18302911464433844481,
-    18231136449196065282,
-    18087586418720506884,
-    17800486357769390088,
-    17226286235867156496,
-    16077885992062689312,
-    13781085504453754944,
-    9187484529235886208,
+    Bitboard(72340172838076926),
+    Bitboard(144680345676153597),
+    Bitboard(289360691352306939),
+    Bitboard(578721382704613623),
+    Bitboard(1157442765409226991),
+    Bitboard(2314885530818453727),
+    Bitboard(4629771061636907199),
+    Bitboard(9259542123273814143),
+    Bitboard(72340172838141441),
+    Bitboard(144680345676217602),
+    Bitboard(289360691352369924),
+    Bitboard(578721382704674568),
+    Bitboard(1157442765409283856),
+    Bitboard(2314885530818502432),
+    Bitboard(4629771061636939584),
+    Bitboard(9259542123273813888),
+    Bitboard(72340172854657281),
+    Bitboard(144680345692602882),
+    Bitboard(289360691368494084),
+    Bitboard(578721382720276488),
+    Bitboard(1157442765423841296),
+    Bitboard(2314885530830970912),
+    Bitboard(4629771061645230144),
+    Bitboard(9259542123273748608),
+    Bitboard(72340177082712321),
+    Bitboard(144680349887234562),
+    Bitboard(289360695496279044),
+    Bitboard(578721386714368008),
+    Bitboard(1157442769150545936),
+    Bitboard(2314885534022901792),
+    Bitboard(4629771063767613504),
+    Bitboard(9259542123257036928),
+    Bitboard(72341259464802561),
+    Bitboard(144681423712944642),
+    Bitboard(289361752209228804),
+    Bitboard(578722409201797128),
+    Bitboard(1157443723186933776),
+    Bitboard(2314886351157207072),
+    Bitboard(4629771607097753664),
+    Bitboard(9259542118978846848),
+    Bitboard(72618349279904001),
+    Bitboard(144956323094725122),
+    Bitboard(289632270724367364),
+    Bitboard(578984165983651848),
+    Bitboard(1157687956502220816),
+    Bitboard(2315095537539358752),
+    Bitboard(4629910699613634624),
+    Bitboard(9259541023762186368),
+    Bitboard(143553341945872641),
+    Bitboard(215330564830528002),
+    Bitboard(358885010599838724),
+    Bitboard(645993902138460168),
+    Bitboard(1220211685215703056),
+    Bitboard(2368647251370188832),
+    Bitboard(4665518383679160384),
+    Bitboard(9259260648297103488),
+    Bitboard(18302911464433844481),
+    Bitboard(18231136449196065282),
+    Bitboard(18087586418720506884),
+    Bitboard(17800486357769390088),
+    Bitboard(17226286235867156496),
+    Bitboard(16077885992062689312),
+    Bitboard(13781085504453754944),
+    Bitboard(9187484529235886208),
 ];
 
 pub static BISHOP_AVECS: AttackVector = [
-    9241421688590303744,
-    36099303471056128,
-    141012904249856,
-    550848566272,
-    6480472064,
-    1108177604608,
-    283691315142656,
-    72624976668147712,
-    4620710844295151618,
-    9241421688590368773,
-    36099303487963146,
-    141017232965652,
-    1659000848424,
-    283693466779728,
-    72624976676520096,
-    145249953336262720,
-    2310355422147510788,
-    4620710844311799048,
-    9241421692918565393,
-    36100411639206946,
-    424704217196612,
-    72625527495610504,
-    145249955479592976,
-    290499906664153120,
-    1155177711057110024,
-    2310355426409252880,
-    4620711952330133792,
-    9241705379636978241,
-    108724279602332802,
-    145390965166737412,
-    290500455356698632,
-    580999811184992272,
-    577588851267340304,
-    1155178802063085600,
-    2310639079102947392,
-    4693335752243822976,
-    9386671504487645697,
-    326598935265674242,
-    581140276476643332,
-    1161999073681608712,
-    288793334762704928,
-    577868148797087808,
-    1227793891648880768,
-    2455587783297826816,
-    4911175566595588352,
-    9822351133174399489,
-    1197958188344280066,
-    2323857683139004420,
-    144117404414255168,
-    360293502378066048,
-    720587009051099136,
-    1441174018118909952,
-    2882348036221108224,
-    5764696068147249408,
-    11529391036782871041,
-    4611756524879479810,
-    567382630219904,
-    1416240237150208,
-    2833579985862656,
-    5667164249915392,
-    11334324221640704,
-    22667548931719168,
-    45053622886727936,
-    18049651735527937,
-];
-
-pub static QUEEN_AVECS: AttackVector = [
-    9313761861428380670,
-    180779649147209725,
-    289501704256556795,
-    578721933553179895,
-    1157442771889699055,
-    2314886638996058335,
-    4630054752952049855,
-    9332167099941961855,
-    4693051017133293059,
-    9386102034266586375,
-    325459994840333070,
-    578862399937640220,
-    1157444424410132280,
-    2315169224285282160,
-    4702396038313459680,
-    9404792076610076608,
-    2382695595002168069,
-    4765391190004401930,
-    9530782384287059477,
-    614821794359483434,
-    1157867469641037908,
-    2387511058326581416,
-    4775021017124823120,
-    9550042029937901728,
-    1227517888139822345,
-    2455035776296487442,
-    4910072647826412836,
-    9820426766351346249,
-    1266167048752878738,
-    2460276499189639204,
-    4920271519124312136,
-    9840541934442029200,
-    649930110732142865,
-    1299860225776030242,
-    2600000831312176196,
-    5272058161445620104,
-    10544115227674579473,
-    2641485286422881314,
-    5210911883574396996,
-    10421541192660455560,
-    361411684042608929,
-    722824471891812930,
-    1517426162373248132,
-    3034571949281478664,
-    6068863523097809168,
-    12137446670713758241,
-    5827868887957914690,
-    11583398706901190788,
-    287670746360127809,
-    575624067208594050,
-    1079472019650937860,
-    2087167920257370120,
-    4102559721436811280,
-    8133343319517438240,
-    16194909420462031425,
-    13871017173176583298,
-    18303478847064064385,
-    18232552689433215490,
-    18090419998706369540,
-    17806153522019305480,
-    17237620560088797200,
-    16100553540994408480,
-    13826139127340482880,
-    9205534180971414145,
+    Bitboard(9241421688590303744),
+    Bitboard(36099303471056128),
+    Bitboard(141012904249856),
+    Bitboard(550848566272),
+    Bitboard(6480472064),
+    Bitboard(1108177604608),
+    Bitboard(283691315142656),
+    Bitboard(72624976668147712),
+    Bitboard(4620710844295151618),
+    Bitboard(9241421688590368773),
+    Bitboard(36099303487963146),
+    Bitboard(141017232965652),
+    Bitboard(1659000848424),
+    Bitboard(283693466779728),
+    Bitboard(72624976676520096),
+    Bitboard(145249953336262720),
+    Bitboard(2310355422147510788),
+    Bitboard(4620710844311799048),
+    Bitboard(9241421692918565393),
+    Bitboard(36100411639206946),
+    Bitboard(424704217196612),
+    Bitboard(72625527495610504),
+    Bitboard(145249955479592976),
+    Bitboard(290499906664153120),
+    Bitboard(1155177711057110024),
+    Bitboard(2310355426409252880),
+    Bitboard(4620711952330133792),
+    Bitboard(9241705379636978241),
+    Bitboard(108724279602332802),
+    Bitboard(145390965166737412),
+    Bitboard(290500455356698632),
+    Bitboard(580999811184992272),
+    Bitboard(577588851267340304),
+    Bitboard(1155178802063085600),
+    Bitboard(2310639079102947392),
+    Bitboard(4693335752243822976),
+    Bitboard(9386671504487645697),
+    Bitboard(326598935265674242),
+    Bitboard(581140276476643332),
+    Bitboard(1161999073681608712),
+    Bitboard(288793334762704928),
+    Bitboard(577868148797087808),
+    Bitboard(1227793891648880768),
+    Bitboard(2455587783297826816),
+    Bitboard(4911175566595588352),
+    Bitboard(9822351133174399489),
+    Bitboard(1197958188344280066),
+    Bitboard(2323857683139004420),
+    Bitboard(144117404414255168),
+    Bitboard(360293502378066048),
+    Bitboard(720587009051099136),
+    Bitboard(1441174018118909952),
+    Bitboard(2882348036221108224),
+    Bitboard(5764696068147249408),
+    Bitboard(11529391036782871041),
+    Bitboard(4611756524879479810),
+    Bitboard(567382630219904),
+    Bitboard(1416240237150208),
+    Bitboard(2833579985862656),
+    Bitboard(5667164249915392),
+    Bitboard(11334324221640704),
+    Bitboard(22667548931719168),
+    Bitboard(45053622886727936),
+    Bitboard(18049651735527937),
 ];
@@ -0,0 +1,17 @@
+use crate::board::Board;
+use crate::movegen::move_rep::MoveList;
+use crate::movegen::pseudolegals::gen_pseudolegal_moves;
+
+// plays each pseudolegal move on a copy of the board and keeps the ones
+// that don't leave our king attacked
+pub fn gen_legal_moves(board: &Board) -> MoveList {
+    let us = board.side_to_move();
+    gen_pseudolegal_moves(board)
+        .into_iter()
+        .filter(|&m| {
+            let mut after = *board;
+            after.make_move(m);
+            !after.in_check(us)
+        })
+        .collect()
+}
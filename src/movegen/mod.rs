@@ -1,3 +1,7 @@
-pub mod attack_vectors;
+pub(crate) mod attack_vectors;
+pub mod legals;
 pub mod move_rep;
-pub mod pseudolegals;
\ No newline at end of file
+pub(crate) mod pseudolegals;
+// the slow mailbox generator the tests and fuzzers check the real one against
+#[doc(hidden)]
+pub mod reference;
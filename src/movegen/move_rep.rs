@@ -1,20 +1,66 @@
-use crate::{
-    board::{Board, pretty_print_index},
-    enums::{Color, PieceType},
-};
+use crate::enums::PieceType;
+use crate::square::Square;
 
 pub type Move = u32;
 // rightmost 6 bits are from-square, then 6 for to-square
 // then 3 bits for from-piece (mover), then 3 for to-piece (captured)
+// then 3 for the promotion piece
 //
+// castling is stored as the king capturing its own rook, so it stays
+// unambiguous in chess960 where the king might not move at all
 
 pub trait MoveExt {
-    fn new(from: u8, to: u8) -> Self;
+    fn new(from: Square, to: Square) -> Self;
+    fn encode(
+        from: Square,
+        to: Square,
+        mover: PieceType,
+        captured: PieceType,
+        promotion: PieceType,
+    ) -> Self;
+    fn from(&self) -> Square;
+    fn to(&self) -> Square;
+    fn mover(&self) -> PieceType;
+    fn captured(&self) -> PieceType;
+    fn promotion(&self) -> PieceType;
 }
 
 impl MoveExt for Move {
-    fn new(from: u8, to: u8) -> Self {
-        from as u32 | ((to as u32) << 6)
+    fn new(from: Square, to: Square) -> Self {
+        from.index() as u32 | ((to.index() as u32) << 6)
+    }
+
+    fn encode(
+        from: Square,
+        to: Square,
+        mover: PieceType,
+        captured: PieceType,
+        promotion: PieceType,
+    ) -> Self {
+        Self::new(from, to)
+            | ((mover as u32) << 12)
+            | ((captured as u32) << 15)
+            | ((promotion as u32) << 18)
+    }
+
+    fn from(&self) -> Square {
+        Square::from_bits(*self)
+    }
+
+    fn to(&self) -> Square {
+        Square::from_bits(self >> 6)
+    }
+
+    fn mover(&self) -> PieceType {
+        PieceType::from_bits(self >> 12)
+    }
+
+    fn captured(&self) -> PieceType {
+        PieceType::from_bits(self >> 15)
+    }
+
+    fn promotion(&self) -> PieceType {
+        PieceType::from_bits(self >> 18)
     }
 }
 
@@ -27,9 +73,7 @@ pub trait MoveListPrettyPrint {
 impl MoveListPrettyPrint for MoveList {
     fn print(&self) {
         for m in self {
-            let from = pretty_print_index((m & 0b111111) as u8);
-            let to = pretty_print_index(((m & 0b111111000000) >> 6) as u8);
-            println!("{} to {}", from, to);
+            println!("{} to {}", m.from(), m.to());
         }
     }
 }
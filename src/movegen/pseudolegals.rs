@@ -1,14 +1,110 @@
 use crate::bitboards::Bitboard;
+use crate::board::Board;
+use crate::enums::{CastleSide, Color, PieceType};
+use crate::movegen::attack_vectors::{CROWNIES_AVECS, HORSEY_AVECS};
+use crate::movegen::move_rep::{Move, MoveExt, MoveList};
+use crate::square::{Rank, Square};
 
-pub fn gen_file_mask(file: u8) -> Bitboard {
-    let mut bb = 0;
-    for _ in 0..8 {
-        bb |= (1 as u64) << file;
-        bb <<= 8;
+pub fn gen_pseudolegal_moves(board: &Board) -> MoveList {
+    let us = board.side_to_move();
+    let c = us as usize;
+    let own = [board.white_bb, board.black_bb][c];
+    let opp = [board.black_bb, board.white_bb][c];
+
+    let mut moves = MoveList::new();
+
+    gen_pawn_moves(board, us, opp, &mut moves);
+
+    let pieces = [
+        (board.knight_bbs[c], PieceType::Knight),
+        (board.bishop_bbs[c], PieceType::Bishop),
+        (board.rook_bbs[c], PieceType::Rook),
+        (board.queen_bbs[c], PieceType::Queen),
+        (board.king_bbs[c], PieceType::King),
+    ];
+    for (bb, piece) in pieces {
+        for from in bb {
+            let attacks = match piece {
+                PieceType::Knight => HORSEY_AVECS[from.index()],
+                PieceType::Bishop => board.get_bishop_attacks(from),
+                PieceType::Rook => board.get_rook_attacks(from),
+                PieceType::Queen => board.get_bishop_attacks(from) | board.get_rook_attacks(from),
+                _ => CROWNIES_AVECS[from.index()],
+            };
+
+            let targets = attacks & !own;
+            for to in targets {
+                let captured = board.piece_at(to).piece;
+                moves.push(Move::encode(from, to, piece, captured, PieceType::None));
+            }
+        }
     }
-    bb
+
+    // castling is written as the king taking its own rook
+    for side in [CastleSide::King, CastleSide::Queen] {
+        if board.can_castle(us, side) {
+            let (king_from, _, rook_from, _) = board.castling_squares(us, side).unwrap();
+            moves.push(Move::encode(
+                king_from,
+                rook_from,
+                PieceType::King,
+                PieceType::None,
+                PieceType::None,
+            ));
+        }
+    }
+
+    moves
 }
 
-pub fn gen_rank_mask(rank: u8) -> Bitboard {
-    0b11111111 << rank
+fn gen_pawn_moves(board: &Board, us: Color, opp: Bitboard, moves: &mut MoveList) {
+    let occupied = board.get_occupied_squares();
+    let (start_rank, promo_rank) = (Rank::R2.relative_to(us), Rank::R8.relative_to(us));
+
+    let mut push = |from: Square, to: Square, captured: PieceType| {
+        if to.rank() == promo_rank {
+            for promotion in [
+                PieceType::Queen,
+                PieceType::Rook,
+                PieceType::Bishop,
+                PieceType::Knight,
+            ] {
+                moves.push(Move::encode(from, to, PieceType::Pawn, captured, promotion));
+            }
+        } else {
+            moves.push(Move::encode(
+                from,
+                to,
+                PieceType::Pawn,
+                captured,
+                PieceType::None,
+            ));
+        }
+    };
+
+    let pawns = board.pawn_bbs[us as usize];
+    for from in pawns {
+        let Some(one) = from.forward(us) else {
+            continue;
+        };
+
+        if !occupied.contains(one) {
+            push(from, one, PieceType::None);
+
+            if let Some(two) = one.forward(us)
+                && from.rank() == start_rank
+                && !occupied.contains(two)
+            {
+                push(from, two, PieceType::None);
+            }
+        }
+
+        for to in [one.offset(-1, 0), one.offset(1, 0)].into_iter().flatten() {
+            if opp.contains(to) {
+                push(from, to, board.piece_at(to).piece);
+            } else if board.ep_square == Some(to) {
+                push(from, to, PieceType::Pawn);
+            }
+        }
+    }
 }
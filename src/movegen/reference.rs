@@ -0,0 +1,269 @@
+use crate::board::{Board, Piece};
+use crate::enums::{CastleSide, Color, PieceType};
+use crate::movegen::move_rep::{Move, MoveExt, MoveList};
+use crate::square::{File, Rank, Square};
+
+// a deliberately slow generator that only walks the mailbox with direction
+// offsets, no bitboards or attack tables. it shares nothing with the real
+// generator apart from make_move, so the two can be checked against each other
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+const KING_STEPS: [(i8, i8); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+const DIAGONALS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+const ORTHOGONALS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+pub fn gen_reference_moves(board: &Board) -> MoveList {
+    let us = board.side_to_move();
+    let mut moves = MoveList::new();
+
+    for from in Square::all() {
+        let p = board.piece_at(from);
+        if p.color != us {
+            continue;
+        }
+        match p.piece {
+            PieceType::Pawn => pawn_moves(board, from, &mut moves),
+            PieceType::Knight => step_moves(board, from, &KNIGHT_STEPS, &mut moves),
+            PieceType::King => step_moves(board, from, &KING_STEPS, &mut moves),
+            PieceType::Bishop => slide_moves(board, from, &DIAGONALS, &mut moves),
+            PieceType::Rook => slide_moves(board, from, &ORTHOGONALS, &mut moves),
+            PieceType::Queen => {
+                slide_moves(board, from, &DIAGONALS, &mut moves);
+                slide_moves(board, from, &ORTHOGONALS, &mut moves);
+            }
+            PieceType::None => {}
+        }
+    }
+
+    for side in [CastleSide::King, CastleSide::Queen] {
+        if let Some(m) = castling_move(board, us, side) {
+            moves.push(m);
+        }
+    }
+
+    moves.retain(|&m| {
+        let mut after = *board;
+        after.make_move(m);
+        find_king(&after.mailbox, us)
+            .is_none_or(|king| !attacked(&after.mailbox, king, us.opponent()))
+    });
+    moves
+}
+
+fn pawn_moves(board: &Board, from: Square, moves: &mut MoveList) {
+    let us = board.side_to_move();
+    let mut push = |to: Square, captured: PieceType| {
+        if to.rank() == Rank::R8.relative_to(us) {
+            for promotion in [
+                PieceType::Queen,
+                PieceType::Rook,
+                PieceType::Bishop,
+                PieceType::Knight,
+            ] {
+                moves.push(Move::encode(from, to, PieceType::Pawn, captured, promotion));
+            }
+        } else {
+            moves.push(Move::encode(
+                from,
+                to,
+                PieceType::Pawn,
+                captured,
+                PieceType::None,
+            ));
+        }
+    };
+
+    let Some(one) = from.forward(us) else {
+        return;
+    };
+    if board.piece_at(one).piece == PieceType::None {
+        push(one, PieceType::None);
+        if from.rank() == Rank::R2.relative_to(us)
+            && let Some(two) = one.forward(us)
+            && board.piece_at(two).piece == PieceType::None
+        {
+            push(two, PieceType::None);
+        }
+    }
+
+    for files in [-1, 1] {
+        let Some(to) = one.offset(files, 0) else {
+            continue;
+        };
+        let target = board.piece_at(to);
+        if target.color == us.opponent() {
+            push(to, target.piece);
+        } else if board.ep_square == Some(to) {
+            push(to, PieceType::Pawn);
+        }
+    }
+}
+
+fn step_moves(board: &Board, from: Square, steps: &[(i8, i8)], moves: &mut MoveList) {
+    let p = board.piece_at(from);
+    for &(files, ranks) in steps {
+        let Some(to) = from.offset(files, ranks) else {
+            continue;
+        };
+        let target = board.piece_at(to);
+        if target.color != p.color {
+            moves.push(Move::encode(
+                from,
+                to,
+                p.piece,
+                target.piece,
+                PieceType::None,
+            ));
+        }
+    }
+}
+
+fn slide_moves(board: &Board, from: Square, directions: &[(i8, i8)], moves: &mut MoveList) {
+    let p = board.piece_at(from);
+    for &(files, ranks) in directions {
+        let mut to = from;
+        while let Some(next) = to.offset(files, ranks) {
+            to = next;
+            let target = board.piece_at(to);
+            if target.color == p.color {
+                break;
+            }
+            moves.push(Move::encode(
+                from,
+                to,
+                p.piece,
+                target.piece,
+                PieceType::None,
+            ));
+            if target.piece != PieceType::None {
+                break;
+            }
+        }
+    }
+}
+
+// written as king-takes-rook like the real generator. the king ends on the
+// g or c file and the rook next to it, wherever they started
+fn castling_move(board: &Board, us: Color, side: CastleSide) -> Option<Move> {
+    let rook_file = board.castling_rights.get(us, side)?;
+    let back_rank = Rank::R1.relative_to(us);
+    let king_from = find_king(&board.mailbox, us)?;
+    let rook_from = Square::new(rook_file, back_rank);
+    let (king_to, rook_to) = match side {
+        CastleSide::King => (File::G, File::F),
+        CastleSide::Queen => (File::C, File::D),
+    };
+
+    let rook = board.piece_at(rook_from);
+    let rook_on_side = match side {
+        CastleSide::King => rook_file > king_from.file(),
+        CastleSide::Queen => rook_file < king_from.file(),
+    };
+    if king_from.rank() != back_rank
+        || !rook_on_side
+        || rook.piece != PieceType::Rook
+        || rook.color != us
+        || attacked(&board.mailbox, king_from, us.opponent())
+    {
+        return None;
+    }
+
+    let files = [king_from.file(), rook_file, king_to, rook_to];
+    let (lo, hi) = (*files.iter().min()?, *files.iter().max()?);
+    for file in File::all().filter(|&f| lo <= f && f <= hi) {
+        let sq = Square::new(file, back_rank);
+        if sq != king_from && sq != rook_from && board.piece_at(sq).piece != PieceType::None {
+            return None;
+        }
+    }
+
+    let mut lifted = board.mailbox;
+    lifted[king_from.index()] = Piece::EMPTY;
+    lifted[rook_from.index()] = Piece::EMPTY;
+    let (lo, hi) = (king_from.file().min(king_to), king_from.file().max(king_to));
+    for file in File::all().filter(|&f| lo <= f && f <= hi) {
+        if attacked(&lifted, Square::new(file, back_rank), us.opponent()) {
+            return None;
+        }
+    }
+
+    Some(Move::encode(
+        king_from,
+        rook_from,
+        PieceType::King,
+        PieceType::None,
+        PieceType::None,
+    ))
+}
+
+fn find_king(mailbox: &[Piece; 64], color: Color) -> Option<Square> {
+    Square::all().find(|sq| {
+        let p = mailbox[sq.index()];
+        p.piece == PieceType::King && p.color == color
+    })
+}
+
+// looks outwards from sq for each kind of piece that could be hitting it
+fn attacked(mailbox: &[Piece; 64], sq: Square, by: Color) -> bool {
+    let holds = |at: Option<Square>, pieces: &[PieceType]| {
+        at.is_some_and(|at| {
+            let p = mailbox[at.index()];
+            p.color == by && pieces.contains(&p.piece)
+        })
+    };
+
+    let pawn_ranks = if by == Color::White { -1 } else { 1 };
+    if [-1, 1]
+        .into_iter()
+        .any(|files| holds(sq.offset(files, pawn_ranks), &[PieceType::Pawn]))
+    {
+        return true;
+    }
+    if KNIGHT_STEPS
+        .iter()
+        .any(|&(f, r)| holds(sq.offset(f, r), &[PieceType::Knight]))
+        || KING_STEPS
+            .iter()
+            .any(|&(f, r)| holds(sq.offset(f, r), &[PieceType::King]))
+    {
+        return true;
+    }
+
+    let sliders = [
+        (DIAGONALS, [PieceType::Bishop, PieceType::Queen]),
+        (ORTHOGONALS, [PieceType::Rook, PieceType::Queen]),
+    ];
+    for (directions, pieces) in sliders {
+        for (files, ranks) in directions {
+            let mut at = sq;
+            while let Some(next) = at.offset(files, ranks) {
+                at = next;
+                if mailbox[at.index()].piece != PieceType::None {
+                    if holds(Some(at), &pieces) {
+                        return true;
+                    }
+                    break;
+                }
+            }
+        }
+    }
+    false
+}
@@ -0,0 +1,201 @@
+use crate::board::Board;
+use crate::enums::{CastleSide, PieceType};
+use crate::movegen::legals::gen_legal_moves;
+use crate::movegen::move_rep::{Move, MoveExt};
+use crate::square::{File, Rank};
+
+pub fn is_castling(board: &Board, m: Move) -> bool {
+    let target = board.piece_at(m.to());
+    m.mover() == PieceType::King
+        && target.piece == PieceType::Rook
+        && target.color == board.side_to_move()
+}
+
+fn promotion_char(piece: PieceType) -> Option<char> {
+    match piece {
+        PieceType::Knight => Some('n'),
+        PieceType::Bishop => Some('b'),
+        PieceType::Rook => Some('r'),
+        PieceType::Queen => Some('q'),
+        _ => None,
+    }
+}
+
+fn promotion_from_char(c: char) -> Option<PieceType> {
+    match c.to_ascii_lowercase() {
+        'n' => Some(PieceType::Knight),
+        'b' => Some(PieceType::Bishop),
+        'r' => Some(PieceType::Rook),
+        'q' => Some(PieceType::Queen),
+        _ => None,
+    }
+}
+
+// castling follows the board's chess960 setting: e1g1 normally, king-takes-rook in chess960
+pub fn move_to_uci(board: &Board, m: Move) -> String {
+    if is_castling(board, m) {
+        return board
+            .castling_move_str(board.side_to_move(), castle_side(m))
+            .unwrap();
+    }
+
+    let mut s = format!("{}{}", m.from(), m.to());
+    if let Some(c) = promotion_char(m.promotion()) {
+        s.push(c);
+    }
+    s
+}
+
+pub fn parse_uci(board: &Board, s: &str) -> Option<Move> {
+    gen_legal_moves(board)
+        .into_iter()
+        .find(|&m| move_to_uci(board, m) == s)
+}
+
+fn castle_side(m: Move) -> CastleSide {
+    if m.to() > m.from() {
+        CastleSide::King
+    } else {
+        CastleSide::Queen
+    }
+}
+
+// accepts the usual sloppiness found in PGN files: check and annotation
+// suffixes, 0-0 for O-O and promotions with or without the '='
+pub fn parse_san(board: &Board, san: &str) -> Option<Move> {
+    let san = san.trim_end_matches(['+', '#', '!', '?']);
+    let legal = gen_legal_moves(board);
+
+    let castle = match san {
+        "O-O" | "0-0" => Some(CastleSide::King),
+        "O-O-O" | "0-0-0" => Some(CastleSide::Queen),
+        _ => None,
+    };
+    if let Some(side) = castle {
+        return legal
+            .into_iter()
+            .find(|&m| is_castling(board, m) && castle_side(m) == side);
+    }
+
+    let mut chars: Vec<char> = san.chars().collect();
+
+    let piece = match chars.first()? {
+        'N' => PieceType::Knight,
+        'B' => PieceType::Bishop,
+        'R' => PieceType::Rook,
+        'Q' => PieceType::Queen,
+        'K' => PieceType::King,
+        _ => PieceType::Pawn,
+    };
+    if piece != PieceType::Pawn {
+        chars.remove(0);
+    }
+
+    let mut promotion = PieceType::None;
+    if let Some(&last) = chars.last()
+        && last.is_ascii_uppercase()
+    {
+        promotion = promotion_from_char(last)?;
+        chars.pop();
+        if chars.last() == Some(&'=') {
+            chars.pop();
+        }
+    }
+
+    if chars.len() < 2 {
+        return None;
+    }
+    let dest: String = chars[chars.len() - 2..].iter().collect();
+    let to = dest.parse().ok()?;
+
+    // whatever is left between the piece letter and the destination is disambiguation
+    let mut from_file = None;
+    let mut from_rank = None;
+    for &c in &chars[..chars.len() - 2] {
+        match c {
+            'a'..='h' => from_file = File::from_char(c),
+            '1'..='8' => from_rank = Rank::from_char(c),
+            'x' | '-' => {}
+            _ => return None,
+        }
+    }
+
+    let mut matches = legal.into_iter().filter(|&m| {
+        let (file, rank) = (m.from().file(), m.from().rank());
+        m.mover() == piece
+            && m.to() == to
+            && m.promotion() == promotion
+            && !is_castling(board, m)
+            && from_file.is_none_or(|f| f == file)
+            && from_rank.is_none_or(|r| r == rank)
+    });
+
+    let m = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+    Some(m)
+}
+
+pub fn move_to_san(board: &Board, m: Move) -> String {
+    let mut san = if is_castling(board, m) {
+        match castle_side(m) {
+            CastleSide::King => "O-O".to_string(),
+            CastleSide::Queen => "O-O-O".to_string(),
+        }
+    } else {
+        let mut san = String::new();
+        let (from_file, from_rank) = (m.from().file(), m.from().rank());
+        let is_capture = m.captured() != PieceType::None;
+
+        if m.mover() == PieceType::Pawn {
+            if is_capture {
+                san.push(from_file.to_char());
+            }
+        } else {
+            san.push(match m.mover() {
+                PieceType::Knight => 'N',
+                PieceType::Bishop => 'B',
+                PieceType::Rook => 'R',
+                PieceType::Queen => 'Q',
+                _ => 'K',
+            });
+
+            let rivals: Vec<Move> = gen_legal_moves(board)
+                .into_iter()
+                .filter(|&o| o.mover() == m.mover() && o.to() == m.to() && o.from() != m.from())
+                .collect();
+            if !rivals.is_empty() {
+                let file_unique = rivals.iter().all(|o| o.from().file() != from_file);
+                let rank_unique = rivals.iter().all(|o| o.from().rank() != from_rank);
+                if file_unique {
+                    san.push(from_file.to_char());
+                } else if rank_unique {
+                    san.push(from_rank.to_char());
+                } else {
+                    san += &m.from().to_string();
+                }
+            }
+        }
+
+        if is_capture {
+            san.push('x');
+        }
+        san += &m.to().to_string();
+        if let Some(c) = promotion_char(m.promotion()) {
+            san.push('=');
+            san.push(c.to_ascii_uppercase());
+        }
+        san
+    };
+
+    let mut after = *board;
+    after.make_move(m);
+    let them = after.side_to_move();
+    if after.in_check(them) {
+        let mated = gen_legal_moves(&after).is_empty();
+        san.push(if mated { '#' } else { '+' });
+    }
+
+    san
+}
@@ -0,0 +1,122 @@
+pub struct PgnGame {
+    pub headers: Vec<(String, String)>,
+    pub moves: Vec<String>, // SAN, main line only
+    pub result: String,
+}
+
+impl PgnGame {
+    fn new() -> Self {
+        Self {
+            headers: Vec::new(),
+            moves: Vec::new(),
+            result: "*".to_string(),
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+// comments, NAGs, move numbers and variations are skipped, only the main line is kept
+pub fn parse_pgn(text: &str) -> Vec<PgnGame> {
+    let mut games = Vec::new();
+    let mut game = PgnGame::new();
+    let mut in_movetext = false;
+
+    let mut brace_comment = false;
+    let mut variation_depth = 0;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('%') {
+            continue;
+        }
+
+        // a well-formed tag pair starts the next game's headers even if this
+        // game left a comment or variation open, so one bad game can't eat the rest
+        let tag = parse_tag(trimmed);
+        if tag.is_some() || !brace_comment && variation_depth == 0 && trimmed.starts_with('[') {
+            // a tag pair after movetext means the previous game ended without a result
+            if in_movetext {
+                games.push(std::mem::replace(&mut game, PgnGame::new()));
+                in_movetext = false;
+            }
+            brace_comment = false;
+            variation_depth = 0;
+            if let Some(tag) = tag {
+                game.headers.push(tag);
+            }
+            continue;
+        }
+
+        let mut token = String::new();
+        let mut chars = trimmed.chars().peekable();
+        while let Some(c) = chars.next() {
+            if brace_comment {
+                brace_comment = c != '}';
+                continue;
+            }
+            match c {
+                '{' => brace_comment = true,
+                ';' => break,
+                '(' => variation_depth += 1,
+                ')' => variation_depth = (variation_depth - 1).max(0),
+                _ if variation_depth > 0 => {}
+                c if c.is_whitespace() => {}
+                _ => {
+                    token.push(c);
+                    let ends = chars
+                        .peek()
+                        .is_none_or(|&n| n.is_whitespace() || "{;()".contains(n));
+                    if !ends {
+                        continue;
+                    }
+
+                    in_movetext = true;
+                    let t = std::mem::take(&mut token);
+                    if RESULTS.contains(&t.as_str()) {
+                        game.result = t;
+                        games.push(std::mem::replace(&mut game, PgnGame::new()));
+                        in_movetext = false;
+                    } else if let Some(san) = strip_move_number(&t) {
+                        game.moves.push(san.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    if in_movetext {
+        games.push(game);
+    }
+
+    games
+}
+
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = inner.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name.to_string(), value.replace("\\\"", "\"")))
+}
+
+// "12.e4" and "12...e5" both show up in the wild; bare numbers and NAGs are dropped
+fn strip_move_number(token: &str) -> Option<&str> {
+    if token.starts_with('$') {
+        return None;
+    }
+    let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = &token[digits..];
+    let san = if rest.starts_with('.') || rest.is_empty() {
+        rest.trim_start_matches('.')
+    } else {
+        token // "0-0" castling starts with a digit too
+    };
+    if san.is_empty() { None } else { Some(san) }
+}
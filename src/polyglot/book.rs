@@ -0,0 +1,225 @@
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::board::Board;
+use crate::enums::PieceType;
+use crate::movegen::move_rep::{Move, MoveExt};
+use crate::polyglot::polyglot_key;
+use crate::square::{File, Rank, Square};
+
+pub const ENTRY_SIZE: usize = 16;
+
+// on disk every field is big-endian: key u64, move u16, weight u16, learn u32
+#[derive(Clone, Copy, PartialEq)]
+pub struct BookEntry {
+    pub key: u64,
+    pub mv: u16,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+impl BookEntry {
+    pub fn from_bytes(bytes: &[u8; ENTRY_SIZE]) -> Self {
+        Self {
+            key: u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            mv: u16::from_be_bytes(bytes[8..10].try_into().unwrap()),
+            weight: u16::from_be_bytes(bytes[10..12].try_into().unwrap()),
+            learn: u32::from_be_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
+
+    pub fn to_bytes(self) -> [u8; ENTRY_SIZE] {
+        let mut bytes = [0; ENTRY_SIZE];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.mv.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct BookMove {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: PieceType,
+}
+
+impl BookMove {
+    // polyglot packs to-file, to-rank, from-file, from-rank and promotion into
+    // 3 bits each, and writes castling as king-takes-rook (e1h1)
+    pub fn decode(mv: u16, board: &Board) -> Self {
+        // file and rank are adjacent 3-bit fields, the same layout as our square index
+        let to = Square::from_bits(mv as u32);
+        let from = Square::from_bits((mv >> 6) as u32);
+        let promotion = match (mv >> 12) & 7 {
+            1 => PieceType::Knight,
+            2 => PieceType::Bishop,
+            3 => PieceType::Rook,
+            4 => PieceType::Queen,
+            _ => PieceType::None,
+        };
+
+        let mover = board.piece_at(from);
+        let target = board.piece_at(to);
+        let castles = mover.piece == PieceType::King
+            && target.piece == PieceType::Rook
+            && target.color == mover.color;
+
+        if castles {
+            return Self::castling(board, from, to);
+        }
+        Self {
+            from,
+            to,
+            promotion,
+        }
+    }
+
+    // for a castling move from our own move generator, which like polyglot
+    // stores it as king-takes-rook
+    pub fn from_castling(board: &Board, m: Move) -> Self {
+        Self::castling(board, m.from(), m.to())
+    }
+
+    // outside chess960 we write castling as the king's own move
+    fn castling(board: &Board, king: Square, rook: Square) -> Self {
+        let to = if board.chess960 {
+            rook
+        } else {
+            let back_rank = Rank::R1.relative_to(board.piece_at(king).color);
+            let king_file = if rook > king { File::G } else { File::C };
+            Square::new(king_file, back_rank)
+        };
+        Self {
+            from: king,
+            to,
+            promotion: PieceType::None,
+        }
+    }
+
+    pub fn encode(&self, board: &Board) -> u16 {
+        let mover = board.piece_at(self.from);
+        let distance = self.from.index().abs_diff(self.to.index());
+
+        // a two-square king move is castling, which polyglot writes as king-takes-rook
+        let to = if mover.piece == PieceType::King && !board.chess960 && distance == 2 {
+            let rook_file = if self.to > self.from {
+                File::H
+            } else {
+                File::A
+            };
+            Square::new(rook_file, self.from.rank())
+        } else {
+            self.to
+        };
+
+        let promotion = match self.promotion {
+            PieceType::Knight => 1,
+            PieceType::Bishop => 2,
+            PieceType::Rook => 3,
+            PieceType::Queen => 4,
+            _ => 0,
+        };
+
+        to.index() as u16 | (self.from.index() as u16) << 6 | promotion << 12
+    }
+
+    pub fn to_uci(self) -> String {
+        let mut s = format!("{}{}", self.from, self.to);
+        match self.promotion {
+            PieceType::Knight => s.push('n'),
+            PieceType::Bishop => s.push('b'),
+            PieceType::Rook => s.push('r'),
+            PieceType::Queen => s.push('q'),
+            _ => {}
+        }
+        s
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BookSelection {
+    BestWeight,
+    WeightedRandom,
+}
+
+pub struct PolyglotBook {
+    entries: Vec<BookEntry>, // sorted by key, as in the file
+}
+
+impl PolyglotBook {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "polyglot book size is not a multiple of 16 bytes",
+            ));
+        }
+
+        let entries = bytes
+            .chunks_exact(ENTRY_SIZE)
+            .map(|chunk| BookEntry::from_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        Ok(Self { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn probe(&self, board: &Board) -> &[BookEntry] {
+        let key = polyglot_key(board);
+        let start = self.entries.partition_point(|e| e.key < key);
+        let end = self.entries.partition_point(|e| e.key <= key);
+        &self.entries[start..end]
+    }
+
+    pub fn pick(&self, board: &Board, selection: BookSelection) -> Option<BookMove> {
+        let entries = self.probe(board);
+        let total: u32 = entries.iter().map(|e| e.weight as u32).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let entry = match selection {
+            BookSelection::BestWeight => entries.iter().max_by_key(|e| e.weight)?,
+            BookSelection::WeightedRandom => {
+                let mut roll = (random_u64() % total as u64) as u32;
+                entries.iter().find(|e| {
+                    if roll < e.weight as u32 {
+                        return true;
+                    }
+                    roll -= e.weight as u32;
+                    false
+                })?
+            }
+        };
+
+        Some(BookMove::decode(entry.mv, board))
+    }
+}
+
+// book picks only need to vary between games, so a time-seeded xorshift is plenty
+fn random_u64() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    let mut x = nanos | 1;
+    for _ in 0..4 {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+    }
+    x
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use crate::board::Board;
+use crate::enums::Color;
+use crate::movegen::move_rep::MoveExt;
+use crate::notation::{is_castling, parse_san};
+use crate::pgn::PgnGame;
+use crate::polyglot::book::{BookEntry, BookMove};
+use crate::polyglot::polyglot_key;
+
+// results are counted from the point of view of the side that played the move
+#[derive(Clone, Copy, Default)]
+pub struct MoveStats {
+    pub count: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MoveStats {
+    // the usual polyglot weighting: a win is worth two draws
+    pub fn points(&self) -> u32 {
+        2 * self.wins + self.draws
+    }
+}
+
+pub struct BookBuildOptions {
+    pub max_ply: usize,
+    pub min_elo: u32,
+    pub min_games: u32,
+    pub only_color: Option<Color>, // for single-colour repertoire books
+}
+
+impl Default for BookBuildOptions {
+    fn default() -> Self {
+        Self {
+            max_ply: 20,
+            min_elo: 0,
+            min_games: 1,
+            only_color: None,
+        }
+    }
+}
+
+pub struct BookBuilder {
+    pub options: BookBuildOptions,
+    pub games_used: u32,
+    pub games_skipped: u32,
+    stats: HashMap<(u64, u16), MoveStats>,
+}
+
+impl BookBuilder {
+    pub fn new(options: BookBuildOptions) -> Self {
+        Self {
+            options,
+            games_used: 0,
+            games_skipped: 0,
+            stats: HashMap::new(),
+        }
+    }
+
+    // replays the game up to max_ply, stopping early at the first move that
+    // doesn't parse. returns whether anything was recorded
+    pub fn add_game(&mut self, game: &PgnGame) -> bool {
+        let white_score = match game.result.as_str() {
+            "1-0" => 2,
+            "1/2-1/2" => 1,
+            "0-1" => 0,
+            _ => {
+                self.games_skipped += 1;
+                return false;
+            }
+        };
+
+        let elo = |name: &str| game.header(name).and_then(|e| e.parse::<u32>().ok());
+        let elos = [elo("WhiteElo"), elo("BlackElo")];
+
+        let mut board = match game.header("FEN").map(Board::from_fen) {
+            Some(Ok(board)) => board,
+            Some(Err(_)) => {
+                self.games_skipped += 1;
+                return false;
+            }
+            None => {
+                let mut board = Board::get_empty_board();
+                board.init();
+                board
+            }
+        };
+        board.chess960 |= game.header("Variant").is_some_and(|v| v.contains("960"));
+
+        let mut recorded = false;
+        for san in game.moves.iter().take(self.options.max_ply) {
+            let Some(m) = parse_san(&board, san) else {
+                break;
+            };
+
+            let us = board.side_to_move();
+            let elo_ok = elos[us as usize].unwrap_or(0) >= self.options.min_elo;
+            let color_ok = self.options.only_color.is_none_or(|c| c == us);
+
+            if elo_ok && color_ok {
+                let score = if us == Color::White {
+                    white_score
+                } else {
+                    2 - white_score
+                };
+
+                let book_move = if is_castling(&board, m) {
+                    BookMove::from_castling(&board, m)
+                } else {
+                    BookMove {
+                        from: m.from(),
+                        to: m.to(),
+                        promotion: m.promotion(),
+                    }
+                };
+                let mv = book_move.encode(&board);
+
+                let stats = self.stats.entry((polyglot_key(&board), mv)).or_default();
+                stats.count += 1;
+                match score {
+                    2 => stats.wins += 1,
+                    1 => stats.draws += 1,
+                    _ => stats.losses += 1,
+                }
+                recorded = true;
+            }
+
+            board.make_move(m);
+        }
+
+        if recorded {
+            self.games_used += 1;
+        } else {
+            self.games_skipped += 1;
+        }
+        recorded
+    }
+
+    // weights are scaled down together if the best move would overflow a u16
+    pub fn entries(&self) -> Vec<BookEntry> {
+        let kept = self
+            .stats
+            .iter()
+            .filter(|(_, s)| s.count >= self.options.min_games && s.points() > 0);
+
+        let max_points = kept.clone().map(|(_, s)| s.points()).max().unwrap_or(0);
+        let scale = |points: u32| {
+            if max_points <= u16::MAX as u32 {
+                points as u16
+            } else {
+                ((points as u64 * u16::MAX as u64) / max_points as u64).max(1) as u16
+            }
+        };
+
+        let mut entries: Vec<BookEntry> = kept
+            .map(|(&(key, mv), s)| BookEntry {
+                key,
+                mv,
+                weight: scale(s.points()),
+                learn: 0,
+            })
+            .collect();
+
+        // probing binary searches on key, and readers expect the best move first
+        entries.sort_by(|a, b| a.key.cmp(&b.key).then(b.weight.cmp(&a.weight)));
+        entries
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<usize> {
+        let entries = self.entries();
+        let bytes: Vec<u8> = entries.iter().flat_map(|e| e.to_bytes()).collect();
+        std::fs::write(path, bytes)?;
+        Ok(entries.len())
+    }
+}
@@ -0,0 +1,56 @@
+pub mod book;
+pub mod builder;
+pub mod randoms;
+
+use crate::board::Board;
+use crate::enums::{CastleSide, Color, PieceType};
+use crate::polyglot::randoms::POLYGLOT_RANDOMS;
+use crate::square::Square;
+
+// the polyglot key is its own zobrist scheme, so books built by other tools can be probed
+pub fn polyglot_key(board: &Board) -> u64 {
+    let mut key = 0;
+
+    for sq in Square::all() {
+        let p = board.piece_at(sq);
+        if p.piece == PieceType::None {
+            continue;
+        }
+        // black pawn, white pawn, black knight, ..., white king
+        let kind = (p.piece as usize - 1) * 2 + (p.color == Color::White) as usize;
+        key ^= POLYGLOT_RANDOMS[64 * kind + sq.index()];
+    }
+
+    let castling = [
+        (Color::White, CastleSide::King),
+        (Color::White, CastleSide::Queen),
+        (Color::Black, CastleSide::King),
+        (Color::Black, CastleSide::Queen),
+    ];
+    for (i, (color, side)) in castling.into_iter().enumerate() {
+        if board.castling_rights.get(color, side).is_some() {
+            key ^= POLYGLOT_RANDOMS[768 + i];
+        }
+    }
+
+    // the en passant file only counts when a pawn is actually there to take
+    if let Some(ep) = board.ep_square {
+        let us = board.side_to_move();
+        let pawns = board.pawn_bbs[us as usize];
+        let can_capture = ep.backward(us).is_some_and(|behind| {
+            [-1, 1]
+                .into_iter()
+                .filter_map(|df| behind.offset(df, 0))
+                .any(|from| pawns.contains(from))
+        });
+        if can_capture {
+            key ^= POLYGLOT_RANDOMS[772 + ep.file().index()];
+        }
+    }
+
+    if board.wtm {
+        key ^= POLYGLOT_RANDOMS[780];
+    }
+
+    key
+}
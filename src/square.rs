@@ -0,0 +1,263 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bitboards::Bitboard;
+use crate::enums::Color;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct File(u8);
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Rank(u8);
+
+// a1 = 0, b1 = 1, ..., h8 = 63, the same order as the bitboards
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Square(u8);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ParseSquareError;
+
+impl File {
+    pub const A: File = File(0);
+    pub const B: File = File(1);
+    pub const C: File = File(2);
+    pub const D: File = File(3);
+    pub const E: File = File(4);
+    pub const F: File = File(5);
+    pub const G: File = File(6);
+    pub const H: File = File(7);
+
+    pub fn new(index: u8) -> Option<Self> {
+        (index < 8).then_some(File(index))
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'a'..='h' => Some(File(c as u8 - b'a')),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> impl DoubleEndedIterator<Item = File> {
+        (0..8).map(File)
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn offset(self, delta: i8) -> Option<Self> {
+        File::new(self.0.checked_add_signed(delta)?)
+    }
+
+    pub fn to_char(self) -> char {
+        (b'a' + self.0) as char
+    }
+
+    pub fn bb(self) -> Bitboard {
+        Bitboard(0x0101_0101_0101_0101 << self.0)
+    }
+}
+
+impl Rank {
+    pub const R1: Rank = Rank(0);
+    pub const R2: Rank = Rank(1);
+    pub const R3: Rank = Rank(2);
+    pub const R4: Rank = Rank(3);
+    pub const R5: Rank = Rank(4);
+    pub const R6: Rank = Rank(5);
+    pub const R7: Rank = Rank(6);
+    pub const R8: Rank = Rank(7);
+
+    pub fn new(index: u8) -> Option<Self> {
+        (index < 8).then_some(Rank(index))
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '1'..='8' => Some(Rank(c as u8 - b'1')),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> impl DoubleEndedIterator<Item = Rank> {
+        (0..8).map(Rank)
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn offset(self, delta: i8) -> Option<Self> {
+        Rank::new(self.0.checked_add_signed(delta)?)
+    }
+
+    // the same rank seen from black's side of the board, so R1 is black's back rank
+    pub fn relative_to(self, color: Color) -> Self {
+        if color == Color::Black {
+            Rank(7 - self.0)
+        } else {
+            self
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        (b'1' + self.0) as char
+    }
+
+    pub fn bb(self) -> Bitboard {
+        Bitboard(0xff << (self.0 * 8))
+    }
+}
+
+impl Square {
+    pub fn new(file: File, rank: Rank) -> Self {
+        Square(rank.0 << 3 | file.0)
+    }
+
+    pub fn from_index(index: u8) -> Option<Self> {
+        (index < 64).then_some(Square(index))
+    }
+
+    // reads a square back out of the low 6 bits of a packed value
+    pub fn from_bits(bits: u32) -> Self {
+        Square((bits & 0b111111) as u8)
+    }
+
+    pub fn all() -> impl DoubleEndedIterator<Item = Square> {
+        (0..64).map(Square)
+    }
+
+    pub fn file(self) -> File {
+        File(self.0 & 7)
+    }
+
+    pub fn rank(self) -> Rank {
+        Rank(self.0 >> 3)
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn bb(self) -> Bitboard {
+        Bitboard(1 << self.0)
+    }
+
+    // None when the step would leave the board, instead of wrapping onto the next rank
+    pub fn offset(self, files: i8, ranks: i8) -> Option<Self> {
+        Some(Square::new(
+            self.file().offset(files)?,
+            self.rank().offset(ranks)?,
+        ))
+    }
+
+    // one step towards the far side of the board for color
+    pub fn forward(self, color: Color) -> Option<Self> {
+        self.offset(0, if color == Color::Black { -1 } else { 1 })
+    }
+
+    pub fn backward(self, color: Color) -> Option<Self> {
+        self.forward(color.opponent())
+    }
+}
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
+impl fmt::Display for ParseSquareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected a square like e4")
+    }
+}
+
+impl std::error::Error for ParseSquareError {}
+
+impl FromStr for Square {
+    type Err = ParseSquareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(f), Some(r), None) => Ok(Square::new(
+                File::from_char(f).ok_or(ParseSquareError)?,
+                Rank::from_char(r).ok_or(ParseSquareError)?,
+            )),
+            _ => Err(ParseSquareError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sq(name: &str) -> Square {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        for square in Square::all() {
+            assert_eq!(square.to_string().parse(), Ok(square));
+        }
+        assert_eq!(sq("a1"), Square(0));
+        assert_eq!(sq("h1"), Square(7));
+        assert_eq!(sq("e4"), Square::new(File::E, Rank::R4));
+        assert_eq!(sq("h8"), Square(63));
+    }
+
+    #[test]
+    fn parse_rejects() {
+        for input in ["i1", "a9", "", "a", "a0", "e44", "E4", "4e", " e4"] {
+            assert_eq!(input.parse::<Square>(), Err(ParseSquareError), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn offsets_stop_at_the_edge() {
+        assert_eq!(sq("h4").offset(1, 0), None);
+        assert_eq!(sq("a4").offset(-1, 0), None);
+        assert_eq!(sq("e8").offset(0, 1), None);
+        assert_eq!(sq("e1").offset(0, -1), None);
+        assert_eq!(sq("a1").offset(-1, -1), None);
+        assert_eq!(sq("h8").offset(1, 1), None);
+        // a knight's jump off the side doesn't land on the next rank
+        assert_eq!(sq("g1").offset(2, 1), None);
+        assert_eq!(sq("g1").offset(-2, 1), Some(sq("e2")));
+
+        assert_eq!(sq("e8").forward(Color::White), None);
+        assert_eq!(sq("e1").forward(Color::Black), None);
+        assert_eq!(sq("e2").forward(Color::White), Some(sq("e3")));
+        assert_eq!(sq("e7").forward(Color::Black), Some(sq("e6")));
+        assert_eq!(sq("e7").backward(Color::Black), Some(sq("e8")));
+    }
+
+    #[test]
+    fn relative_to() {
+        assert_eq!(Rank::R1.relative_to(Color::White), Rank::R1);
+        assert_eq!(Rank::R1.relative_to(Color::Black), Rank::R8);
+        assert_eq!(Rank::R3.relative_to(Color::Black), Rank::R6);
+        for rank in Rank::all() {
+            assert_eq!(
+                rank.relative_to(Color::Black).relative_to(Color::Black),
+                rank
+            );
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::bitboards::Bitboard;
+use crate::board::Board;
+use crate::enums::{Color, PieceType};
+use crate::square::{File, Rank, Square};
+
+const LIGHT: &str = "#f0d9b5";
+const DARK: &str = "#b58863";
+const LIGHT_MARKED: &str = "#cdd26a";
+const DARK_MARKED: &str = "#aaa23a";
+const ARROW: &str = "#15781b";
+
+pub struct SvgOptions {
+    pub square_size: u32,
+    pub coordinates: bool,
+    pub flipped: bool,
+    pub highlight: Bitboard, // tinted squares, e.g. the last move
+    pub marks: Bitboard,     // a dot on each square, e.g. an attack map
+    pub arrows: Vec<(Square, Square)>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            square_size: 45,
+            coordinates: true,
+            flipped: false,
+            highlight: Bitboard::EMPTY,
+            marks: Bitboard::EMPTY,
+            arrows: Vec::new(),
+        }
+    }
+}
+
+// the pieces are the filled chess glyphs drawn as text, so nothing has to be
+// embedded, but how they look depends on the fonts of whoever opens the file.
+// U+FE0E asks for the text form, otherwise browsers draw the pawn as a colour
+// emoji that ignores the fill
+fn glyph(piece: PieceType) -> &'static str {
+    match piece {
+        PieceType::Pawn => "♟\u{fe0e}",
+        PieceType::Knight => "♞\u{fe0e}",
+        PieceType::Bishop => "♝\u{fe0e}",
+        PieceType::Rook => "♜\u{fe0e}",
+        PieceType::Queen => "♛\u{fe0e}",
+        PieceType::King => "♚\u{fe0e}",
+        PieceType::None => "",
+    }
+}
+
+impl Board {
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let s = options.square_size as f32;
+        let size = 8.0 * s;
+
+        // top left corner of a square as seen by the viewer
+        let corner = |sq: Square| {
+            let (col, row) = if options.flipped {
+                (7 - sq.file().index(), sq.rank().index())
+            } else {
+                (sq.file().index(), 7 - sq.rank().index())
+            };
+            (col as f32 * s, row as f32 * s)
+        };
+        let centre = |sq: Square| {
+            let (x, y) = corner(sq);
+            (x + s / 2.0, y + s / 2.0)
+        };
+
+        let mut svg = String::new();
+        let mut out = |line: String| {
+            svg += &line;
+            svg.push('\n');
+        };
+
+        out(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size:.1}" height="{size:.1}" viewBox="0 0 {size:.1} {size:.1}">"#
+        ));
+        out(format!(
+            r#"<defs><marker id="arrowhead" viewBox="0 0 4 4" refX="2" refY="2" markerWidth="4" markerHeight="4" orient="auto"><path d="M0,0 L4,2 L0,4 z" fill="{ARROW}"/></marker></defs>"#
+        ));
+
+        for sq in Square::all() {
+            let (x, y) = corner(sq);
+            let light = (sq.file().index() + sq.rank().index()) % 2 == 1;
+            let fill = match (light, options.highlight.contains(sq)) {
+                (true, false) => LIGHT,
+                (false, false) => DARK,
+                (true, true) => LIGHT_MARKED,
+                (false, true) => DARK_MARKED,
+            };
+            out(format!(
+                r#"<rect x="{x:.1}" y="{y:.1}" width="{s:.1}" height="{s:.1}" fill="{fill}"/>"#
+            ));
+        }
+
+        if options.coordinates {
+            // ranks down the left edge and files along the bottom, in the
+            // colour of the other kind of square so they stay readable
+            let font = s * 0.25;
+            let (left, bottom) = if options.flipped {
+                (File::H, Rank::R8)
+            } else {
+                (File::A, Rank::R1)
+            };
+            for rank in Rank::all() {
+                let sq = Square::new(left, rank);
+                let (x, y) = corner(sq);
+                let light = (left.index() + rank.index()) % 2 == 1;
+                out(format!(
+                    r#"<text x="{:.1}" y="{:.1}" font-size="{font:.1}" font-family="sans-serif" fill="{}">{rank}</text>"#,
+                    x + s * 0.05,
+                    y + font,
+                    if light { DARK } else { LIGHT }
+                ));
+            }
+            for file in File::all() {
+                let sq = Square::new(file, bottom);
+                let (x, y) = corner(sq);
+                let light = (file.index() + bottom.index()) % 2 == 1;
+                out(format!(
+                    r#"<text x="{:.1}" y="{:.1}" font-size="{font:.1}" font-family="sans-serif" text-anchor="end" fill="{}">{file}</text>"#,
+                    x + s * 0.95,
+                    y + s * 0.95,
+                    if light { DARK } else { LIGHT }
+                ));
+            }
+        }
+
+        for sq in Square::all() {
+            let p = self.piece_at(sq);
+            if p.piece == PieceType::None {
+                continue;
+            }
+            let (x, y) = centre(sq);
+            let fill = if p.color == Color::White {
+                "white"
+            } else {
+                "black"
+            };
+            out(format!(
+                r#"<text x="{x:.1}" y="{:.1}" font-size="{:.1}" text-anchor="middle" dominant-baseline="central" fill="{fill}" stroke="black" stroke-width="{:.1}">{}</text>"#,
+                y + s * 0.05,
+                s * 0.85,
+                s * 0.02,
+                glyph(p.piece)
+            ));
+        }
+
+        for sq in options.marks {
+            let (x, y) = centre(sq);
+            out(format!(
+                r#"<circle cx="{x:.1}" cy="{y:.1}" r="{:.1}" fill="{ARROW}" fill-opacity="0.5"/>"#,
+                s * 0.15
+            ));
+        }
+
+        // stop the line short so the head ends near the middle of the square
+        for &(from, to) in &options.arrows {
+            let (x1, y1) = centre(from);
+            let (x2, y2) = centre(to);
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let length = (dx * dx + dy * dy).sqrt();
+            if length == 0.0 {
+                continue;
+            }
+            let shorten = s * 0.3 / length;
+            out(format!(
+                r#"<line x1="{x1:.1}" y1="{y1:.1}" x2="{:.1}" y2="{:.1}" stroke="{ARROW}" stroke-width="{:.1}" stroke-opacity="0.8" marker-end="url(#arrowhead)"/>"#,
+                x2 - dx * shorten,
+                y2 - dy * shorten,
+                s * 0.15
+            ));
+        }
+
+        out("</svg>".to_string());
+        svg
+    }
+}
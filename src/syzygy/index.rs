@@ -0,0 +1,126 @@
+use std::sync::OnceLock;
+
+// the lookup tables behind the Syzygy position index. squares are plain
+// indices here, a1 = 0 to h8 = 63, since the index does arithmetic on them
+pub(crate) struct IndexTables {
+    // binomial[k][n] ways to choose k of n squares
+    pub binomial: [[u64; 64]; 7],
+    // a2-h7 to 0..47, highest for the pawn nearest the edge and lowest rank
+    pub map_pawns: [usize; 64],
+    // index of the first leading pawn, and how many indices each file takes
+    pub lead_pawn_idx: [[u64; 64]; 6],
+    pub lead_pawns_size: [[u64; 4]; 6],
+    // the b1-h1-h7 triangle to 0..27
+    pub map_b1h1h7: [usize; 64],
+    // the a1-d1-d4 triangle to 0..9, with the diagonal last
+    pub map_a1d1d4: [usize; 64],
+    // the 462 ways to place two kings with the first in the a1-d1-d4 triangle
+    pub map_kk: [[u64; 64]; 10],
+}
+
+// rank minus file, zero on the a1-h8 diagonal and negative below it
+pub(crate) fn off_a1h8(sq: usize) -> i32 {
+    (sq / 8) as i32 - (sq % 8) as i32
+}
+
+pub(crate) fn tables() -> &'static IndexTables {
+    static TABLES: OnceLock<IndexTables> = OnceLock::new();
+    TABLES.get_or_init(IndexTables::new)
+}
+
+impl IndexTables {
+    fn new() -> Self {
+        let mut t = IndexTables {
+            binomial: [[0; 64]; 7],
+            map_pawns: [0; 64],
+            lead_pawn_idx: [[0; 64]; 6],
+            lead_pawns_size: [[0; 4]; 6],
+            map_b1h1h7: [0; 64],
+            map_a1d1d4: [usize::MAX; 64],
+            map_kk: [[0; 64]; 10],
+        };
+
+        for n in 0..64 {
+            t.binomial[0][n] = 1;
+            for k in 1..7 {
+                if n > 0 {
+                    t.binomial[k][n] = t.binomial[k - 1][n - 1] + t.binomial[k][n - 1];
+                }
+            }
+        }
+
+        let mut code = 0;
+        for sq in 0..64 {
+            if off_a1h8(sq) < 0 {
+                t.map_b1h1h7[sq] = code;
+                code += 1;
+            }
+        }
+
+        let mut code = 0;
+        let mut diagonal = Vec::new();
+        for sq in 0..=27 {
+            if sq % 8 > 3 {
+                continue;
+            }
+            if off_a1h8(sq) < 0 {
+                t.map_a1d1d4[sq] = code;
+                code += 1;
+            } else if off_a1h8(sq) == 0 {
+                diagonal.push(sq);
+            }
+        }
+        for sq in diagonal {
+            t.map_a1d1d4[sq] = code;
+            code += 1;
+        }
+
+        // with the first king on the diagonal the second one stays on or
+        // below it, and positions with both on the diagonal come last
+        let mut both_on_diagonal = Vec::new();
+        let mut code = 0;
+        for idx in 0..10 {
+            let Some(s1) = (0..64).find(|&sq| t.map_a1d1d4[sq] == idx) else {
+                continue;
+            };
+            for s2 in 0..64 {
+                let touching = (s1 % 8).abs_diff(s2 % 8) <= 1 && (s1 / 8).abs_diff(s2 / 8) <= 1;
+                if touching || off_a1h8(s1) == 0 && off_a1h8(s2) > 0 {
+                    continue;
+                }
+                if off_a1h8(s1) == 0 && off_a1h8(s2) == 0 {
+                    both_on_diagonal.push((idx, s2));
+                } else {
+                    t.map_kk[idx][s2] = code;
+                    code += 1;
+                }
+            }
+        }
+        for (idx, s2) in both_on_diagonal {
+            t.map_kk[idx][s2] = code;
+            code += 1;
+        }
+
+        // the leading pawn is the one with the highest map_pawns, so any other
+        // pawn has that many squares left to go on
+        let mut available = 48;
+        for lead_count in 1..=5 {
+            for file in 0..4 {
+                let mut idx = 0;
+                for rank in 1..7 {
+                    let sq = rank * 8 + file;
+                    if lead_count == 1 {
+                        available -= 2;
+                        t.map_pawns[sq] = available + 1;
+                        t.map_pawns[sq ^ 7] = available;
+                    }
+                    t.lead_pawn_idx[lead_count][sq] = idx;
+                    idx += t.binomial[lead_count - 1][t.map_pawns[sq]];
+                }
+                t.lead_pawns_size[lead_count][file] = idx;
+            }
+        }
+
+        t
+    }
+}
@@ -0,0 +1,308 @@
+mod index;
+mod table;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::ops::Neg;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::board::Board;
+use crate::enums::PieceType;
+use crate::movegen::legals::gen_legal_moves;
+use crate::movegen::move_rep::MoveExt;
+use table::{Table, TableKind, material_name};
+
+// win, draw or loss for the side to move. a cursed win is a win that the
+// fifty-move rule turns into a draw, a blessed loss the other way round
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Wdl {
+    Loss,
+    BlessedLoss,
+    Draw,
+    CursedWin,
+    Win,
+}
+
+impl Wdl {
+    // the tables store the result plus 2
+    pub(crate) fn from_stored(value: u16) -> Option<Self> {
+        Some(match value {
+            0 => Wdl::Loss,
+            1 => Wdl::BlessedLoss,
+            2 => Wdl::Draw,
+            3 => Wdl::CursedWin,
+            4 => Wdl::Win,
+            _ => return None,
+        })
+    }
+
+    fn signum(self) -> i32 {
+        match self {
+            Wdl::Loss | Wdl::BlessedLoss => -1,
+            Wdl::Draw => 0,
+            Wdl::CursedWin | Wdl::Win => 1,
+        }
+    }
+}
+
+impl Neg for Wdl {
+    type Output = Wdl;
+
+    fn neg(self) -> Wdl {
+        match self {
+            Wdl::Loss => Wdl::Win,
+            Wdl::BlessedLoss => Wdl::CursedWin,
+            Wdl::Draw => Wdl::Draw,
+            Wdl::CursedWin => Wdl::BlessedLoss,
+            Wdl::Win => Wdl::Loss,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ProbeError {
+    Castling,
+    MissingTable(String),
+    BadTable(String),
+}
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProbeError::Castling => write!(f, "tablebases don't cover castling rights"),
+            ProbeError::MissingTable(file) => write!(f, "no tablebase file {file}"),
+            ProbeError::BadTable(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+// the files for one material balance, read the first time they're probed
+#[derive(Default)]
+struct TableFiles {
+    wdl_path: Option<PathBuf>,
+    dtz_path: Option<PathBuf>,
+    wdl: OnceLock<Result<Table, String>>,
+    dtz: OnceLock<Result<Table, String>>,
+}
+
+// Syzygy endgame tablebases. only the side to move and the pieces matter,
+// positions with castling rights can't be probed and the fifty-move
+// counter is ignored
+#[derive(Default)]
+pub struct Tablebase {
+    tables: HashMap<String, TableFiles>,
+    max_pieces: u32,
+}
+
+impl Tablebase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // a UCI SyzygyPath: directories separated by ':', or ';' on Windows
+    pub fn open(syzygy_path: &str) -> io::Result<Self> {
+        let mut tablebase = Self::new();
+        for dir in std::env::split_paths(syzygy_path) {
+            tablebase.add_directory(&dir)?;
+        }
+        Ok(tablebase)
+    }
+
+    // returns how many table files were found
+    pub fn add_directory(&mut self, dir: &Path) -> io::Result<usize> {
+        let mut found = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let (Some(stem), Some(ext)) = (path.file_stem(), path.extension()) else {
+                continue;
+            };
+            let Some(name) = stem.to_str().filter(|s| is_table_name(s)) else {
+                continue;
+            };
+            let files = self.tables.entry(name.to_string()).or_default();
+            match ext.to_str() {
+                Some("rtbw") => files.wdl_path = Some(path.clone()),
+                Some("rtbz") => files.dtz_path = Some(path.clone()),
+                _ => continue,
+            }
+            self.max_pieces = self.max_pieces.max(name.len() as u32 - 1);
+            found += 1;
+        }
+        Ok(found)
+    }
+
+    // the most pieces, kings included, of any table found
+    pub fn max_pieces(&self) -> u32 {
+        self.max_pieces
+    }
+
+    pub fn probe_wdl(&self, board: &Board) -> Result<Wdl, ProbeError> {
+        if !board.castling_rights().is_empty() {
+            return Err(ProbeError::Castling);
+        }
+        Ok(self.search(board, false)?.0)
+    }
+
+    // plies to the next capture, pawn move or mate with best play, positive
+    // when the side to move wins and 0 for a draw. wins and losses the
+    // fifty-move rule spoils are pushed out by another 100 plies. the count
+    // can be one too high when the position could have gone either way
+    pub fn probe_dtz(&self, board: &Board) -> Result<i32, ProbeError> {
+        if !board.castling_rights().is_empty() {
+            return Err(ProbeError::Castling);
+        }
+
+        let (wdl, zeroing) = self.search(board, true)?;
+        if wdl == Wdl::Draw {
+            return Ok(0);
+        }
+        // the table stores don't-care values when the best move zeroes the
+        // counter, so nothing to look up
+        if zeroing {
+            return Ok(dtz_before_zeroing(wdl));
+        }
+
+        let table = self.table(board, TableKind::Dtz)?;
+        if let Some(dtz) = table.probe_dtz(board, wdl).map_err(ProbeError::BadTable)? {
+            let cursed = matches!(wdl, Wdl::CursedWin | Wdl::BlessedLoss);
+            return Ok((dtz + 100 * cursed as i32) * wdl.signum());
+        }
+
+        // the table only has the other side to move, so look one ply ahead
+        // for the move that keeps the count lowest
+        let mut best = None;
+        for m in gen_legal_moves(board) {
+            let zeroing = m.captured() != PieceType::None || m.mover() == PieceType::Pawn;
+            let mut after = *board;
+            after.make_move(m);
+
+            let mut dtz = if zeroing {
+                -dtz_before_zeroing(self.search(&after, false)?.0)
+            } else {
+                -self.probe_dtz(&after)?
+            };
+            if dtz == 1
+                && after.in_check(after.side_to_move())
+                && gen_legal_moves(&after).is_empty()
+            {
+                best = Some(1);
+            }
+            if !zeroing {
+                dtz += dtz.signum();
+            }
+            if dtz.signum() == wdl.signum() && best.is_none_or(|b| dtz < b) {
+                best = Some(dtz);
+            }
+        }
+        Ok(best.unwrap_or(-1))
+    }
+
+    // the tables store anything in positions where a capture (or, for DTZ,
+    // any zeroing move) wins, and may store a loss where a capture draws, so
+    // those moves are searched first. also says whether the best move zeroes
+    fn search(&self, board: &Board, check_zeroing: bool) -> Result<(Wdl, bool), ProbeError> {
+        let moves = gen_legal_moves(board);
+        let mut best = Wdl::Loss;
+        let mut searched = 0;
+
+        for &m in &moves {
+            let zeroing =
+                m.captured() != PieceType::None || check_zeroing && m.mover() == PieceType::Pawn;
+            if !zeroing {
+                continue;
+            }
+            searched += 1;
+
+            let mut after = *board;
+            after.make_move(m);
+            let value = -self.search(&after, false)?.0;
+            if value > best {
+                best = value;
+                if value == Wdl::Win {
+                    return Ok((value, true));
+                }
+            }
+        }
+
+        // with every move searched, the stored value isn't needed (and is
+        // wrong for en passant, which the tables don't know about)
+        let no_more_moves = searched > 0 && searched == moves.len();
+        let value = if no_more_moves {
+            best
+        } else {
+            self.probe_wdl_table(board)?
+        };
+
+        if best >= value {
+            return Ok((best, best > Wdl::Draw || no_more_moves));
+        }
+        Ok((value, false))
+    }
+
+    fn probe_wdl_table(&self, board: &Board) -> Result<Wdl, ProbeError> {
+        if board.get_occupied_squares().popcount() == 2 {
+            return Ok(Wdl::Draw);
+        }
+        self.table(board, TableKind::Wdl)?
+            .probe_wdl(board)
+            .map_err(ProbeError::BadTable)
+    }
+
+    fn table(&self, board: &Board, kind: TableKind) -> Result<&Table, ProbeError> {
+        let ext = match kind {
+            TableKind::Wdl => "rtbw",
+            TableKind::Dtz => "rtbz",
+        };
+        // the files are named with the stronger side first
+        let name = material_name(board);
+        let (white, black) = name.split_once('v').unwrap();
+        let flipped = format!("{black}v{white}");
+        let missing = || ProbeError::MissingTable(format!("{name}.{ext}"));
+
+        let (name, files) = [&name, &flipped]
+            .into_iter()
+            .find_map(|n| Some((n, self.tables.get(n)?)))
+            .ok_or_else(missing)?;
+        let (path, cell) = match kind {
+            TableKind::Wdl => (&files.wdl_path, &files.wdl),
+            TableKind::Dtz => (&files.dtz_path, &files.dtz),
+        };
+        let path = path.as_ref().ok_or_else(missing)?;
+
+        let table = cell.get_or_init(|| {
+            let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+            Table::parse(bytes, name, kind)
+        });
+        table
+            .as_ref()
+            .map_err(|e| ProbeError::BadTable(format!("{}: {e}", path.display())))
+    }
+}
+
+// tables go up to seven pieces, kings included
+fn is_table_name(name: &str) -> bool {
+    let Some((white, black)) = name.split_once('v') else {
+        return false;
+    };
+    if white.len() + black.len() > 7 {
+        return false;
+    }
+    [white, black]
+        .iter()
+        .all(|side| side.starts_with('K') && side[1..].chars().all(|c| "QRBNP".contains(c)))
+}
+
+// the count just before a capture or pawn move with the given result
+fn dtz_before_zeroing(wdl: Wdl) -> i32 {
+    match wdl {
+        Wdl::Win => 1,
+        Wdl::CursedWin => 101,
+        Wdl::Draw => 0,
+        Wdl::BlessedLoss => -101,
+        Wdl::Loss => -1,
+    }
+}
@@ -0,0 +1,731 @@
+use crate::bitboards::Bitboard;
+use crate::board::Board;
+use crate::enums::{Color, PieceType};
+use crate::square::Square;
+use crate::syzygy::Wdl;
+use crate::syzygy::index::{off_a1h8, tables};
+
+pub(crate) const WDL_MAGIC: [u8; 4] = [0x71, 0xe8, 0x23, 0x5d];
+pub(crate) const DTZ_MAGIC: [u8; 4] = [0xd7, 0x66, 0x0c, 0xa5];
+
+// the first byte after the magic
+pub(crate) const SPLIT: u8 = 1;
+pub(crate) const HAS_PAWNS: u8 = 2;
+
+// per sub-table flags
+pub(crate) const STM: u8 = 1;
+pub(crate) const MAPPED: u8 = 2;
+pub(crate) const WIN_PLIES: u8 = 4;
+pub(crate) const LOSS_PLIES: u8 = 8;
+pub(crate) const WIDE: u8 = 16;
+pub(crate) const SINGLE_VALUE: u8 = 128;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum TableKind {
+    Wdl,
+    Dtz,
+}
+
+// one compressed run of values, a table has one per side to move and, with
+// pawns, per file of the leading pawn. offsets point into the file's bytes
+#[derive(Default)]
+struct PairsData {
+    flags: u8,
+    // piece codes in the order they're indexed: the piece type with the
+    // colour in bit 3, the same numbering the files use
+    pieces: Vec<u8>,
+    group_len: Vec<usize>,
+    // the multiplier of each group, the last entry is the table size
+    group_idx: Vec<u64>,
+    block_size: usize,
+    span: u64,
+    sparse_index: usize,
+    sparse_index_size: usize,
+    block_lengths: usize,
+    block_length_size: usize,
+    blocks_num: usize,
+    data: usize,
+    // holds the value itself in a single-value table
+    min_sym_len: u8,
+    lowest_sym: usize,
+    base64: Vec<u64>,
+    // how many values each symbol expands to, minus one
+    symlen: Vec<u8>,
+    btree: usize,
+    // offsets of the four DTZ value maps
+    map_idx: [usize; 4],
+}
+
+// a table file, read in full. the name (KRvKP) is written with the side it
+// calls white first, the position being probed may have the colours swapped
+pub(crate) struct Table {
+    kind: TableKind,
+    name: String,
+    symmetric: bool,
+    has_pawns: bool,
+    has_unique_pieces: bool,
+    // pawns of the leading colour, then of the other one
+    pawn_counts: [usize; 2],
+    piece_count: usize,
+    bytes: Vec<u8>,
+    // [side to move][file of the leading pawn]
+    pairs: Vec<Vec<PairsData>>,
+}
+
+// the table name for a position with white's pieces first, like KQvKR
+pub(crate) fn material_name(board: &Board) -> String {
+    let side = |color| {
+        let mut s = String::new();
+        for (piece, c) in [
+            (PieceType::King, 'K'),
+            (PieceType::Queen, 'Q'),
+            (PieceType::Rook, 'R'),
+            (PieceType::Bishop, 'B'),
+            (PieceType::Knight, 'N'),
+            (PieceType::Pawn, 'P'),
+        ] {
+            for _ in 0..board.pieces(color, piece).popcount() {
+                s.push(c);
+            }
+        }
+        s
+    };
+    format!("{}v{}", side(Color::White), side(Color::Black))
+}
+
+fn piece_code(c: char) -> Option<u8> {
+    Some(match c {
+        'P' => PieceType::Pawn,
+        'N' => PieceType::Knight,
+        'B' => PieceType::Bishop,
+        'R' => PieceType::Rook,
+        'Q' => PieceType::Queen,
+        'K' => PieceType::King,
+        _ => return None,
+    } as u8)
+}
+
+fn byte(bytes: &[u8], at: usize) -> Result<u8, String> {
+    bytes.get(at).copied().ok_or_else(truncated)
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Result<u16, String> {
+    Ok(u16::from_le_bytes([byte(bytes, at)?, byte(bytes, at + 1)?]))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Result<u32, String> {
+    let b = bytes.get(at..at + 4).ok_or_else(truncated)?;
+    Ok(u32::from_le_bytes(b.try_into().unwrap()))
+}
+
+// reads past the end of a block are fine, they just come back as zeroes
+fn u32_be(bytes: &[u8], at: usize) -> u32 {
+    let mut b = [0; 4];
+    for (i, x) in b.iter_mut().enumerate() {
+        *x = bytes.get(at + i).copied().unwrap_or(0);
+    }
+    u32::from_be_bytes(b)
+}
+
+fn truncated() -> String {
+    "table is truncated".to_string()
+}
+
+fn corrupt() -> String {
+    "corrupt table".to_string()
+}
+
+impl Table {
+    pub(crate) fn parse(bytes: Vec<u8>, name: &str, kind: TableKind) -> Result<Self, String> {
+        let (white, black) = name.split_once('v').ok_or("bad table name")?;
+        let mut codes = Vec::new();
+        for (side, color) in [(white, 0), (black, 8)] {
+            for c in side.chars() {
+                codes.push(piece_code(c).ok_or("bad table name")? | color);
+            }
+        }
+        let count = |code: u8| codes.iter().filter(|&&c| c == code).count();
+        let pawn = PieceType::Pawn as u8;
+
+        // with pawns on both sides the side with fewer of them leads,
+        // it compresses better
+        let (white_pawns, black_pawns) = (count(pawn), count(pawn | 8));
+        let white_leads = black_pawns == 0 || white_pawns > 0 && black_pawns >= white_pawns;
+        let pawn_counts = if white_leads {
+            [white_pawns, black_pawns]
+        } else {
+            [black_pawns, white_pawns]
+        };
+
+        let mut table = Table {
+            kind,
+            name: name.to_string(),
+            symmetric: white == black,
+            has_pawns: white_pawns + black_pawns > 0,
+            has_unique_pieces: codes
+                .iter()
+                .any(|&c| c & 7 != PieceType::King as u8 && count(c) == 1),
+            pawn_counts,
+            piece_count: codes.len(),
+            bytes,
+            pairs: Vec::new(),
+        };
+
+        let magic = match kind {
+            TableKind::Wdl => WDL_MAGIC,
+            TableKind::Dtz => DTZ_MAGIC,
+        };
+        if table.bytes.get(..4) != Some(&magic) {
+            return Err("not a Syzygy table, the magic number is wrong".to_string());
+        }
+        table.parse_header(&codes)?;
+        Ok(table)
+    }
+
+    fn parse_header(&mut self, codes: &[u8]) -> Result<(), String> {
+        let bytes = &self.bytes;
+        let flags = byte(bytes, 4)?;
+        if (flags & HAS_PAWNS != 0) != self.has_pawns || (flags & SPLIT != 0) == self.symmetric {
+            return Err(format!("table doesn't match {}", self.name));
+        }
+
+        // a DTZ table only stores one side to move
+        let sides = if self.kind == TableKind::Wdl && !self.symmetric {
+            2
+        } else {
+            1
+        };
+        let files = if self.has_pawns { 4 } else { 1 };
+        let pp = self.has_pawns && self.pawn_counts[1] > 0;
+
+        let mut pairs: Vec<Vec<PairsData>> = (0..sides)
+            .map(|_| (0..files).map(|_| PairsData::default()).collect())
+            .collect();
+
+        let mut at = 5;
+        for file in 0..files {
+            let order = |side: usize, b: u8| if side == 0 { b & 0xf } else { b >> 4 };
+            let first = byte(bytes, at)?;
+            let second = if pp { byte(bytes, at + 1)? } else { 0xff };
+            at += 1 + pp as usize;
+
+            for k in 0..self.piece_count {
+                let b = byte(bytes, at + k)?;
+                for (side, d) in pairs.iter_mut().enumerate() {
+                    d[file].pieces.push(order(side, b));
+                }
+            }
+            at += self.piece_count;
+
+            for (side, d) in pairs.iter_mut().enumerate() {
+                let mut pieces = d[file].pieces.clone();
+                let mut expected = codes.to_vec();
+                pieces.sort_unstable();
+                expected.sort_unstable();
+                if pieces != expected {
+                    return Err(format!("table pieces don't match {}", self.name));
+                }
+                let order = [order(side, first), order(side, second)];
+                self.set_groups(&mut d[file], order, file)?;
+            }
+        }
+        at += at & 1;
+
+        for file in 0..files {
+            for d in pairs.iter_mut() {
+                at = set_sizes(bytes, &mut d[file], at)?;
+            }
+        }
+
+        if self.kind == TableKind::Dtz {
+            for d in &mut pairs[0] {
+                if d.flags & MAPPED == 0 {
+                    continue;
+                }
+                if d.flags & WIDE != 0 {
+                    at += at & 1;
+                    for i in 0..4 {
+                        d.map_idx[i] = at + 2;
+                        at += 2 * u16_le(bytes, at)? as usize + 2;
+                    }
+                } else {
+                    for i in 0..4 {
+                        d.map_idx[i] = at + 1;
+                        at += byte(bytes, at)? as usize + 1;
+                    }
+                }
+            }
+            at += at & 1;
+        }
+
+        for file in 0..files {
+            for d in pairs.iter_mut() {
+                d[file].sparse_index = at;
+                at += d[file].sparse_index_size * 6;
+            }
+        }
+        for file in 0..files {
+            for d in pairs.iter_mut() {
+                d[file].block_lengths = at;
+                at += d[file].block_length_size * 2;
+            }
+        }
+        for file in 0..files {
+            for d in pairs.iter_mut() {
+                at = (at + 0x3f) & !0x3f;
+                d[file].data = at;
+                let size = d[file].blocks_num.checked_mul(d[file].block_size);
+                at = size.and_then(|s| at.checked_add(s)).ok_or_else(corrupt)?;
+            }
+        }
+        if at > bytes.len() {
+            return Err(truncated());
+        }
+
+        self.pairs = pairs;
+        Ok(())
+    }
+
+    // splits the pieces into groups that are indexed together: the leading
+    // pawns or the first two or three pieces, then runs of identical pieces.
+    // order says where in the index the leading group and the other side's
+    // pawns go, the rest fill in around them
+    fn set_groups(&self, d: &mut PairsData, order: [u8; 2], file: usize) -> Result<(), String> {
+        let t = tables();
+        let mut first_len: i32 = if self.has_pawns {
+            0
+        } else if self.has_unique_pieces {
+            3
+        } else {
+            2
+        };
+
+        d.group_len = vec![1];
+        for i in 1..self.piece_count {
+            first_len -= 1;
+            if first_len > 0 || d.pieces[i] == d.pieces[i - 1] {
+                *d.group_len.last_mut().unwrap() += 1;
+            } else {
+                d.group_len.push(1);
+            }
+        }
+
+        let pp = self.has_pawns && self.pawn_counts[1] > 0;
+        let n = d.group_len.len();
+        let mut next = if pp { 2 } else { 1 };
+        let mut free = 64 - d.group_len[0] - if pp { d.group_len[1] } else { 0 };
+        let mut idx: u64 = 1;
+        d.group_idx = vec![0; n + 1];
+
+        let bad = || "bad group order".to_string();
+        let mut k = 0;
+        while next < n || k == order[0] || k == order[1] {
+            if k == order[0] {
+                d.group_idx[0] = idx;
+                idx *= if self.has_pawns {
+                    t.lead_pawns_size[d.group_len[0]][file]
+                } else if self.has_unique_pieces {
+                    31332
+                } else {
+                    462
+                };
+            } else if k == order[1] {
+                d.group_idx[1] = idx;
+                idx *= t.binomial[d.group_len[1]][48 - d.group_len[0]];
+            } else {
+                let len = d.group_len[next];
+                d.group_idx[next] = idx;
+                idx *= t.binomial.get(len).ok_or_else(bad)?[free];
+                free = free.checked_sub(len).ok_or_else(bad)?;
+                next += 1;
+            }
+            k += 1;
+            if k > 15 {
+                return Err(bad());
+            }
+        }
+        d.group_idx[n] = idx;
+        Ok(())
+    }
+
+    // which side and file the position is stored under, and at what index.
+    // None when this is a DTZ table for the other side to move
+    pub(crate) fn index(&self, board: &Board) -> Option<(usize, usize, u64)> {
+        let t = tables();
+        let black_to_move = board.side_to_move() == Color::Black;
+        // symmetric tables only store white to move, and the rest are
+        // written with the stronger side as white
+        let flip = self.symmetric && black_to_move || material_name(board) != self.name;
+        let flip_color = if flip { 8 } else { 0 };
+        let flip_squares = if flip { 56 } else { 0 };
+        let stm = (flip != black_to_move) as usize;
+
+        let mut squares: Vec<usize> = Vec::with_capacity(self.piece_count);
+        let mut pieces: Vec<u8> = Vec::with_capacity(self.piece_count);
+        let mut lead_pawns = Bitboard::EMPTY;
+        let mut file = 0;
+
+        // with pawns, the leading pawn (nearest the edge, then lowest rank)
+        // picks which of the four per-file tables to use
+        if self.has_pawns {
+            let pc = self.pairs[0][0].pieces[0] ^ flip_color;
+            let color = if pc & 8 == 0 {
+                Color::White
+            } else {
+                Color::Black
+            };
+            lead_pawns = board.pieces(color, PieceType::Pawn);
+            for sq in Square::all().filter(|&sq| lead_pawns.contains(sq)) {
+                squares.push(sq.index() ^ flip_squares);
+                pieces.push(pc);
+            }
+            let lead = (0..squares.len()).max_by_key(|&i| t.map_pawns[squares[i]])?;
+            squares.swap(0, lead);
+            file = (squares[0] % 8).min(7 - squares[0] % 8);
+        }
+        let lead_count = squares.len();
+
+        if self.kind == TableKind::Dtz {
+            let stored = (self.pairs[0][file].flags & STM) as usize;
+            if stored != stm && (self.has_pawns || !self.symmetric) {
+                return None;
+            }
+        }
+
+        let others = board.get_occupied_squares() & !lead_pawns;
+        for sq in Square::all().filter(|&sq| others.contains(sq)) {
+            let p = board.piece_at(sq);
+            squares.push(sq.index() ^ flip_squares);
+            pieces.push((p.piece as u8 | (p.color as u8) << 3) ^ flip_color);
+        }
+
+        let side = if self.pairs.len() == 1 { 0 } else { stm };
+        let d = &self.pairs[side][file];
+
+        // put the pieces in the order the table indexes them
+        for i in lead_count..squares.len().saturating_sub(1) {
+            if let Some(j) = (i + 1..squares.len()).find(|&j| d.pieces[i] == pieces[j]) {
+                pieces.swap(i, j);
+                squares.swap(i, j);
+            }
+        }
+
+        // then mirror so the leading piece is on the a-d files
+        if squares[0] % 8 > 3 {
+            for sq in &mut squares {
+                *sq ^= 7;
+            }
+        }
+
+        let mut idx;
+        if self.has_pawns {
+            idx = t.lead_pawn_idx[lead_count][squares[0]];
+            squares[1..lead_count].sort_by_key(|&sq| t.map_pawns[sq]);
+            for (i, &sq) in squares.iter().enumerate().take(lead_count).skip(1) {
+                idx += t.binomial[i][t.map_pawns[sq]];
+            }
+        } else {
+            // without pawns the board can be turned so the leading piece is on
+            // ranks 1-4, and the first piece off the a1-h8 diagonal below it
+            if squares[0] / 8 > 3 {
+                for sq in &mut squares {
+                    *sq ^= 56;
+                }
+            }
+            for i in 0..d.group_len[0] {
+                let off = off_a1h8(squares[i]);
+                if off == 0 {
+                    continue;
+                }
+                if off > 0 {
+                    for sq in &mut squares[i..] {
+                        *sq = ((*sq >> 3) | (*sq << 3)) & 63;
+                    }
+                }
+                break;
+            }
+
+            let s = &squares;
+            idx = if self.has_unique_pieces {
+                let adjust1 = (s[1] > s[0]) as u64;
+                let adjust2 = (s[2] > s[0]) as u64 + (s[2] > s[1]) as u64;
+                let rank = |sq: usize| (sq / 8) as u64;
+
+                if off_a1h8(s[0]) != 0 {
+                    (t.map_a1d1d4[s[0]] as u64 * 63 + (s[1] as u64 - adjust1)) * 62 + s[2] as u64
+                        - adjust2
+                } else if off_a1h8(s[1]) != 0 {
+                    (6 * 63 + rank(s[0]) * 28 + t.map_b1h1h7[s[1]] as u64) * 62 + s[2] as u64
+                        - adjust2
+                } else if off_a1h8(s[2]) != 0 {
+                    6 * 63 * 62
+                        + 4 * 28 * 62
+                        + rank(s[0]) * 7 * 28
+                        + (rank(s[1]) - adjust1) * 28
+                        + t.map_b1h1h7[s[2]] as u64
+                } else {
+                    6 * 63 * 62
+                        + 4 * 28 * 62
+                        + 4 * 7 * 28
+                        + rank(s[0]) * 7 * 6
+                        + (rank(s[1]) - adjust1) * 6
+                        + (rank(s[2]) - adjust2)
+                }
+            } else {
+                t.map_kk[t.map_a1d1d4[s[0]]][s[1]]
+            };
+        }
+
+        // the remaining groups, each as a combination of the squares the
+        // earlier groups left free
+        idx *= d.group_idx[0];
+        let mut start = d.group_len[0];
+        let mut remaining_pawns = self.has_pawns && self.pawn_counts[1] > 0;
+        for next in 1..d.group_len.len() {
+            let len = d.group_len[next];
+            squares[start..start + len].sort_unstable();
+            let mut n = 0;
+            for i in 0..len {
+                let sq = squares[start + i];
+                let adjust = squares[..start].iter().filter(|&&s| sq > s).count();
+                n += t.binomial[i + 1][sq - adjust - 8 * remaining_pawns as usize];
+            }
+            remaining_pawns = false;
+            idx += n * d.group_idx[next];
+            start += len;
+        }
+
+        Some((side, file, idx))
+    }
+
+    // the raw value stored for a position, see index()
+    fn value(&self, side: usize, file: usize, idx: u64) -> Result<u16, String> {
+        decompress_pairs(&self.bytes, &self.pairs[side][file], idx)
+    }
+
+    pub(crate) fn probe_wdl(&self, board: &Board) -> Result<Wdl, String> {
+        let (side, file, idx) = self.index(board).ok_or("not a WDL table")?;
+        Wdl::from_stored(self.value(side, file, idx)?).ok_or_else(|| "bad WDL value".to_string())
+    }
+
+    // plies to a capture, pawn move or mate, always positive. None when the
+    // table has the other side to move
+    pub(crate) fn probe_dtz(&self, board: &Board, wdl: Wdl) -> Result<Option<i32>, String> {
+        let Some((side, file, idx)) = self.index(board) else {
+            return Ok(None);
+        };
+        let d = &self.pairs[side][file];
+        let mut value = self.value(side, file, idx)? as usize;
+
+        if d.flags & MAPPED != 0 {
+            // the maps are for win, loss, cursed win and blessed loss
+            let map = match wdl {
+                Wdl::Win | Wdl::Draw => 0,
+                Wdl::Loss => 1,
+                Wdl::CursedWin => 2,
+                Wdl::BlessedLoss => 3,
+            };
+            value = if d.flags & WIDE != 0 {
+                u16_le(&self.bytes, d.map_idx[map] + 2 * value)? as usize
+            } else {
+                byte(&self.bytes, d.map_idx[map] + value)? as usize
+            };
+        }
+
+        // unless the flags say otherwise, values are in moves, not plies
+        let in_moves = match wdl {
+            Wdl::Win => d.flags & WIN_PLIES == 0,
+            Wdl::Loss => d.flags & LOSS_PLIES == 0,
+            _ => true,
+        };
+        if in_moves {
+            value *= 2;
+        }
+        Ok(Some(value as i32 + 1))
+    }
+}
+
+fn set_sizes(bytes: &[u8], d: &mut PairsData, mut at: usize) -> Result<usize, String> {
+    d.flags = byte(bytes, at)?;
+    if d.flags & SINGLE_VALUE != 0 {
+        d.min_sym_len = byte(bytes, at + 1)?;
+        return Ok(at + 2);
+    }
+
+    let size = *d.group_idx.last().unwrap();
+    // both sizes are powers of two, stored as the exponent
+    d.block_size = 1usize
+        .checked_shl(byte(bytes, at + 1)? as u32)
+        .ok_or_else(corrupt)?;
+    d.span = 1u64
+        .checked_shl(byte(bytes, at + 2)? as u32)
+        .ok_or_else(corrupt)?;
+    d.sparse_index_size = size.div_ceil(d.span) as usize;
+    let padding = byte(bytes, at + 3)? as usize;
+    d.blocks_num = u32_le(bytes, at + 4)? as usize;
+    d.block_length_size = d.blocks_num + padding;
+    let max_sym_len = byte(bytes, at + 8)?;
+    d.min_sym_len = byte(bytes, at + 9)?;
+    if d.min_sym_len == 0 || max_sym_len < d.min_sym_len || max_sym_len > 32 {
+        return Err("bad symbol lengths".to_string());
+    }
+    at += 10;
+    d.lowest_sym = at;
+
+    // a canonical Huffman code where longer codes have lower values. base64[i]
+    // is the first code of length min_sym_len + i, left-aligned in 64 bits
+    let lengths = (max_sym_len - d.min_sym_len) as usize + 1;
+    d.base64 = vec![0; lengths];
+    for i in (0..lengths - 1).rev() {
+        let lowest = u16_le(bytes, at + 2 * i)? as u64;
+        let next = u16_le(bytes, at + 2 * i + 2)? as u64;
+        d.base64[i] = (d.base64[i + 1] + lowest)
+            .checked_sub(next)
+            .ok_or_else(corrupt)?
+            / 2;
+    }
+    for (i, base) in d.base64.iter_mut().enumerate() {
+        *base <<= 64 - i - d.min_sym_len as usize;
+    }
+    at += 2 * lengths;
+
+    // symbols are either a value or a pair of other symbols (recursive pairing)
+    let symbols = u16_le(bytes, at)? as usize;
+    at += 2;
+    d.btree = at;
+    d.symlen = vec![0; symbols];
+    let mut state = vec![Visit::New; symbols];
+    for sym in 0..symbols {
+        if state[sym] == Visit::New {
+            d.symlen[sym] = set_symlen(bytes, d, sym, &mut state)?;
+        }
+    }
+    Ok(at + 3 * symbols + (symbols & 1))
+}
+
+// the left and right halves of a symbol, 12 bits each. a right half of 0xfff
+// marks a plain value, which is then the left half
+fn btree(bytes: &[u8], d: &PairsData, sym: usize) -> Result<(usize, usize), String> {
+    let at = d.btree + 3 * sym;
+    let (a, b, c) = (byte(bytes, at)?, byte(bytes, at + 1)?, byte(bytes, at + 2)?);
+    let left = ((b as usize & 0xf) << 8) | a as usize;
+    let right = ((c as usize) << 4) | (b as usize >> 4);
+    Ok((left, right))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    New,
+    Open,
+    Done,
+}
+
+// a symbol that contains itself would never finish expanding
+fn set_symlen(
+    bytes: &[u8],
+    d: &mut PairsData,
+    sym: usize,
+    state: &mut [Visit],
+) -> Result<u8, String> {
+    state[sym] = Visit::Open;
+    let (left, right) = btree(bytes, d, sym)?;
+    if right == 0xfff {
+        state[sym] = Visit::Done;
+        return Ok(0);
+    }
+    if left >= d.symlen.len() || right >= d.symlen.len() {
+        return Err(corrupt());
+    }
+    for half in [left, right] {
+        match state[half] {
+            Visit::New => d.symlen[half] = set_symlen(bytes, d, half, state)?,
+            Visit::Open => return Err(corrupt()),
+            Visit::Done => {}
+        }
+    }
+    state[sym] = Visit::Done;
+    Ok(d.symlen[left]
+        .saturating_add(d.symlen[right])
+        .saturating_add(1))
+}
+
+fn decompress_pairs(bytes: &[u8], d: &PairsData, idx: u64) -> Result<u16, String> {
+    if d.flags & SINGLE_VALUE != 0 {
+        return Ok(d.min_sym_len as u16);
+    }
+    // the sparse index gives the block and offset of every span-th value,
+    // counted from the middle of the span. walk the block lengths from there
+    let k = (idx / d.span) as usize;
+    if k >= d.sparse_index_size {
+        return Err(corrupt());
+    }
+    let entry = d.sparse_index + 6 * k;
+    let mut block = u32_le(bytes, entry)? as usize;
+    let mut offset = u16_le(bytes, entry + 4)? as i64;
+    offset += (idx % d.span) as i64 - (d.span / 2) as i64;
+
+    let block_length = |block: usize| -> Result<i64, String> {
+        if block >= d.block_length_size {
+            return Err(corrupt());
+        }
+        Ok(u16_le(bytes, d.block_lengths + 2 * block)? as i64)
+    };
+    while offset < 0 {
+        block = block.checked_sub(1).ok_or_else(corrupt)?;
+        offset += block_length(block)? + 1;
+    }
+    while offset > block_length(block)? {
+        offset -= block_length(block)? + 1;
+        block += 1;
+    }
+    if block >= d.blocks_num {
+        return Err(corrupt());
+    }
+
+    // read symbols from the start of the block until the one covering offset
+    let min_len = d.min_sym_len as usize;
+    let mut at = d.data + block * d.block_size;
+    let mut buf = (u32_be(bytes, at) as u64) << 32 | u32_be(bytes, at + 4) as u64;
+    at += 8;
+    let mut buf_size = 64;
+    let mut sym;
+    loop {
+        let mut len = 0;
+        while buf < d.base64[len] {
+            len += 1;
+            if len == d.base64.len() {
+                return Err(corrupt());
+            }
+        }
+        sym = ((buf - d.base64[len]) >> (64 - len - min_len)) as usize;
+        sym += u16_le(bytes, d.lowest_sym + 2 * len)? as usize;
+        if sym >= d.symlen.len() {
+            return Err(corrupt());
+        }
+
+        if offset < d.symlen[sym] as i64 + 1 {
+            break;
+        }
+        offset -= d.symlen[sym] as i64 + 1;
+        len += min_len;
+        buf <<= len;
+        buf_size -= len;
+        if buf_size <= 32 {
+            buf_size += 32;
+            buf |= (u32_be(bytes, at) as u64) << (64 - buf_size);
+            at += 4;
+        }
+    }
+
+    // then expand the pairs down to the single value at offset
+    while d.symlen[sym] != 0 {
+        let (left, right) = btree(bytes, d, sym)?;
+        if offset < d.symlen[left] as i64 + 1 {
+            sym = left;
+        } else {
+            offset -= d.symlen[left] as i64 + 1;
+            sym = right;
+        }
+    }
+    Ok(btree(bytes, d, sym)?.0 as u16)
+}
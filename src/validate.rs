@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::board::Board;
+use crate::enums::{CastleSide, Color, PieceType};
+use crate::square::{Rank, Square};
+
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PositionError {
+    KingCount(Color, u32),
+    PawnOnBackRank(Square),
+    OpponentInCheck,
+    // the castling right has no king and rook on the back rank to go with it
+    BadCastlingRights(Color, CastleSide),
+    BadEnPassant(Square),
+    // the bitboards say something else is on this square than the mailbox does
+    MailboxMismatch(Square),
+    // more pieces than promotions could have produced
+    TooManyPieces(Color),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionError::KingCount(color, n) => {
+                write!(f, "{color:?} has {n} kings instead of one")
+            }
+            PositionError::PawnOnBackRank(sq) => {
+                write!(f, "pawn on {sq}, on the first or last rank")
+            }
+            PositionError::OpponentInCheck => write!(f, "the side not to move is in check"),
+            PositionError::BadCastlingRights(color, side) => write!(
+                f,
+                "{color:?} can't castle {side:?}side, the king or rook isn't in place"
+            ),
+            PositionError::BadEnPassant(sq) => {
+                write!(f, "en passant on {sq} doesn't follow a double pawn push")
+            }
+            PositionError::MailboxMismatch(sq) => {
+                write!(f, "mailbox and bitboards disagree on {sq}")
+            }
+            PositionError::TooManyPieces(color) => {
+                write!(f, "{color:?} has more pieces than promotions allow")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+impl Board {
+    // checks for positions that can't come up in a game, so they're caught
+    // before the move generator runs into a board without a king and the like.
+    // every problem found is reported, not just the first
+    pub fn validate(&self) -> Result<(), Vec<PositionError>> {
+        let mut errors = Vec::new();
+
+        errors.extend(
+            self.mailbox_mismatches()
+                .map(PositionError::MailboxMismatch),
+        );
+
+        let mut kings_ok = true;
+        for color in [Color::White, Color::Black] {
+            let kings = self.pieces(color, PieceType::King).popcount();
+            if kings != 1 {
+                errors.push(PositionError::KingCount(color, kings));
+                kings_ok = false;
+            }
+            if self.too_many_pieces(color) {
+                errors.push(PositionError::TooManyPieces(color));
+            }
+        }
+
+        let back_ranks = Rank::R1.bb() | Rank::R8.bb();
+        let pawns = self.pawn_bbs[0] | self.pawn_bbs[1];
+        errors.extend(
+            (pawns & back_ranks)
+                .into_iter()
+                .map(PositionError::PawnOnBackRank),
+        );
+
+        // with a king missing or doubled there's no sensible answer to these
+        if kings_ok {
+            if self.in_check(self.side_to_move().opponent()) {
+                errors.push(PositionError::OpponentInCheck);
+            }
+            for color in [Color::White, Color::Black] {
+                for side in [CastleSide::King, CastleSide::Queen] {
+                    if !self.castling_right_ok(color, side) {
+                        errors.push(PositionError::BadCastlingRights(color, side));
+                    }
+                }
+            }
+        }
+
+        if let Some(ep) = self.ep_square
+            && !self.ep_square_ok(ep)
+        {
+            errors.push(PositionError::BadEnPassant(ep));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // panics if the two copies of the position have drifted apart. make_move
+    // calls it after every move when built with the consistency-checks feature
+    #[cfg(any(debug_assertions, feature = "consistency-checks"))]
+    pub fn assert_consistent(&self) {
+        let mismatches: Vec<String> = self.mailbox_mismatches().map(|sq| sq.to_string()).collect();
+        assert!(
+            mismatches.is_empty(),
+            "mailbox and bitboards disagree on {} in {}",
+            mismatches.join(" "),
+            self.to_fen()
+        );
+        if let Some(ep) = self.ep_square {
+            assert!(
+                !self.get_occupied_squares().contains(ep),
+                "en passant square {ep} is occupied in {}",
+                self.to_fen()
+            );
+        }
+    }
+
+    // squares where the piece bitboards don't hold exactly the mailbox piece,
+    // or the colour bitboards don't hold exactly its colour
+    pub(crate) fn mailbox_mismatches(&self) -> impl Iterator<Item = Square> + '_ {
+        Square::all().filter(|&sq| {
+            let p = self.piece_at(sq);
+            let pieces_ok = [Color::White, Color::Black].into_iter().all(|color| {
+                PIECE_TYPES.into_iter().all(|piece| {
+                    let expected = p.color == color && p.piece == piece;
+                    self.pieces(color, piece).contains(sq) == expected
+                })
+            });
+            let colors_ok = self.white_bb.contains(sq) == (p.color == Color::White)
+                && self.black_bb.contains(sq) == (p.color == Color::Black);
+            let filled_ok = (p.piece == PieceType::None) == (p.color == Color::None);
+            !(pieces_ok && colors_ok && filled_ok)
+        })
+    }
+
+    // every piece beyond the starting set has to be a promoted pawn
+    fn too_many_pieces(&self, color: Color) -> bool {
+        let count = |piece| self.pieces(color, piece).popcount();
+        let pawns = count(PieceType::Pawn);
+        let promoted = count(PieceType::Knight).saturating_sub(2)
+            + count(PieceType::Bishop).saturating_sub(2)
+            + count(PieceType::Rook).saturating_sub(2)
+            + count(PieceType::Queen).saturating_sub(1);
+        pawns > 8 || pawns + promoted > 8
+    }
+
+    fn castling_right_ok(&self, color: Color, side: CastleSide) -> bool {
+        if self.castling_rights.get(color, side).is_none() {
+            return true;
+        }
+        self.castling_squares(color, side)
+            .is_some_and(|(_, _, rook_from, _)| {
+                let rook = self.piece_at(rook_from);
+                rook.piece == PieceType::Rook && rook.color == color
+            })
+    }
+
+    // the square a pawn just skipped over: empty, with that pawn in front of it
+    // and its starting square behind it empty too
+    fn ep_square_ok(&self, ep: Square) -> bool {
+        let us = self.side_to_move();
+        let them = us.opponent();
+        if ep.rank() != Rank::R6.relative_to(us) {
+            return false;
+        }
+
+        let occupied = self.get_occupied_squares();
+        let pushed = ep.backward(us).unwrap();
+        let origin = ep.forward(us).unwrap();
+        !occupied.contains(ep)
+            && !occupied.contains(origin)
+            && self.pieces(them, PieceType::Pawn).contains(pushed)
+    }
+}
@@ -0,0 +1,61 @@
+use rustybattler::bitboards::Bitboard;
+use rustybattler::{File, Rank, Square};
+
+fn bb(squares: &str) -> Bitboard {
+    squares
+        .split_whitespace()
+        .map(|name| name.parse::<Square>().unwrap())
+        .collect()
+}
+
+#[test]
+fn shifts_move_one_square() {
+    let e4 = bb("e4");
+    assert_eq!(e4.north(), bb("e5"));
+    assert_eq!(e4.south(), bb("e3"));
+    assert_eq!(e4.east(), bb("f4"));
+    assert_eq!(e4.west(), bb("d4"));
+    assert_eq!(e4.north_east(), bb("f5"));
+    assert_eq!(e4.north_west(), bb("d5"));
+    assert_eq!(e4.south_east(), bb("f3"));
+    assert_eq!(e4.south_west(), bb("d3"));
+}
+
+#[test]
+fn shifts_drop_squares_off_the_edge() {
+    assert_eq!(File::H.bb().east(), Bitboard::EMPTY);
+    assert_eq!(File::A.bb().west(), Bitboard::EMPTY);
+    assert_eq!(Rank::R8.bb().north(), Bitboard::EMPTY);
+    assert_eq!(Rank::R1.bb().south(), Bitboard::EMPTY);
+
+    let a1 = bb("a1");
+    assert_eq!(a1.south_west(), Bitboard::EMPTY);
+    assert_eq!(a1.north_west(), Bitboard::EMPTY);
+    assert_eq!(a1.south_east(), Bitboard::EMPTY);
+    assert_eq!(a1.north_east(), bb("b2"));
+
+    let h8 = bb("h8");
+    assert_eq!(h8.north_east(), Bitboard::EMPTY);
+    assert_eq!(h8.south_east(), Bitboard::EMPTY);
+    assert_eq!(h8.south_west(), bb("g7"));
+
+    // the h-file doesn't wrap round onto the a-file a rank up
+    assert_eq!(bb("h4 a4").north_east(), bb("b5"));
+    assert_eq!(bb("h4 a4").south_west(), bb("g3"));
+}
+
+#[test]
+fn display_grid() {
+    let expected = [
+        "8 .  .  .  .  .  .  .  # ",
+        "7 .  .  .  .  .  .  .  . ",
+        "6 .  .  .  .  .  .  .  . ",
+        "5 .  .  .  .  .  .  .  . ",
+        "4 .  .  .  .  #  .  .  . ",
+        "3 .  .  .  .  .  .  .  . ",
+        "2 .  .  .  .  .  .  .  . ",
+        "1 #  .  .  .  .  .  .  . ",
+        "  a  b  c  d  e  f  g  h",
+    ];
+    assert_eq!(bb("a1 e4 h8").to_string(), expected.join("\n"));
+}
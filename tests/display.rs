@@ -0,0 +1,100 @@
+use rustybattler::{Board, DisplayOptions, STARTING_FEN, Square, parse_uci};
+
+fn start() -> Board {
+    Board::from_fen(STARTING_FEN).unwrap()
+}
+
+fn after_e4() -> Board {
+    let mut board = start();
+    board.make_move(parse_uci(&board, "e2e4").unwrap());
+    board
+}
+
+// every line of the output ends in a newline, the file letters' included
+fn lines(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+#[test]
+fn start_position_ascii() {
+    let expected = lines(&[
+        "8 r  n  b  q  k  b  n  r ",
+        "7 p  p  p  p  p  p  p  p ",
+        "6 .  .  .  .  .  .  .  . ",
+        "5 .  .  .  .  .  .  .  . ",
+        "4 .  .  .  .  .  .  .  . ",
+        "3 .  .  .  .  .  .  .  . ",
+        "2 P  P  P  P  P  P  P  P ",
+        "1 R  N  B  Q  K  B  N  R ",
+        "  a  b  c  d  e  f  g  h ",
+        "white to move, castling KQkq",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    ]);
+    assert_eq!(start().to_string(), expected);
+}
+
+#[test]
+fn start_position_unicode() {
+    let options = DisplayOptions {
+        unicode: true,
+        info: false,
+        ..Default::default()
+    };
+    let expected = lines(&[
+        "8 ♜  ♞  ♝  ♛  ♚  ♝  ♞  ♜ ",
+        "7 ♟  ♟  ♟  ♟  ♟  ♟  ♟  ♟ ",
+        "6 ·  ·  ·  ·  ·  ·  ·  · ",
+        "5 ·  ·  ·  ·  ·  ·  ·  · ",
+        "4 ·  ·  ·  ·  ·  ·  ·  · ",
+        "3 ·  ·  ·  ·  ·  ·  ·  · ",
+        "2 ♙  ♙  ♙  ♙  ♙  ♙  ♙  ♙ ",
+        "1 ♖  ♘  ♗  ♕  ♔  ♗  ♘  ♖ ",
+        "  a  b  c  d  e  f  g  h ",
+    ]);
+    assert_eq!(start().display(options).to_string(), expected);
+}
+
+#[test]
+fn flipped_puts_black_at_the_bottom() {
+    let options = DisplayOptions {
+        flipped: true,
+        info: false,
+        ..Default::default()
+    };
+    let expected = lines(&[
+        "1 R  N  B  K  Q  B  N  R ",
+        "2 P  P  P  .  P  P  P  P ",
+        "3 .  .  .  .  .  .  .  . ",
+        "4 .  .  .  P  .  .  .  . ",
+        "5 .  .  .  .  .  .  .  . ",
+        "6 .  .  .  .  .  .  .  . ",
+        "7 p  p  p  p  p  p  p  p ",
+        "8 r  n  b  k  q  b  n  r ",
+        "  h  g  f  e  d  c  b  a ",
+    ]);
+    assert_eq!(after_e4().display(options).to_string(), expected);
+}
+
+#[test]
+fn highlight_and_info_lines() {
+    let e2: Square = "e2".parse().unwrap();
+    let e4: Square = "e4".parse().unwrap();
+    let options = DisplayOptions {
+        highlight: e2.bb() | e4.bb(),
+        ..Default::default()
+    };
+    let expected = lines(&[
+        "8 r  n  b  q  k  b  n  r ",
+        "7 p  p  p  p  p  p  p  p ",
+        "6 .  .  .  .  .  .  .  . ",
+        "5 .  .  .  .  .  .  .  . ",
+        "4 .  .  .  . [P] .  .  . ",
+        "3 .  .  .  .  .  .  .  . ",
+        "2 P  P  P  P [.] P  P  P ",
+        "1 R  N  B  Q  K  B  N  R ",
+        "  a  b  c  d  e  f  g  h ",
+        "black to move, castling KQkq",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    ]);
+    assert_eq!(after_e4().display(options).to_string(), expected);
+}
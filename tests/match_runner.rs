@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use rustybattler::match_runner::game::{GameResult, PlayedGame, TimeControl};
+use rustybattler::match_runner::stats::{MatchScore, Sprt, SprtStatus, elo_to_score, score_to_elo};
+use rustybattler::match_runner::{PgnHeaders, write_pgn};
+use rustybattler::{STARTING_FEN, chess960_fen};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+}
+
+#[test]
+fn time_control_parses() {
+    let tc = TimeControl::parse("60+0.6").unwrap();
+    assert_eq!(tc.base, Duration::from_secs(60));
+    assert_eq!(tc.increment, Duration::from_millis(600));
+
+    let tc = TimeControl::parse("10").unwrap();
+    assert_eq!(tc.base, Duration::from_secs(10));
+    assert_eq!(tc.increment, Duration::ZERO);
+}
+
+#[test]
+fn bad_time_controls_are_rejected() {
+    // none of these fit in a Duration, from_secs_f64 would panic on them
+    for tc in ["-5", "nan", "inf", "1e30", "5+-1", "5+nan", "", "5+", "abc"] {
+        assert!(TimeControl::parse(tc).is_none(), "{tc}");
+    }
+}
+
+fn score(wins: u32, losses: u32, draws: u32) -> MatchScore {
+    MatchScore {
+        wins,
+        losses,
+        draws,
+    }
+}
+
+#[test]
+fn elo_from_scores() {
+    assert_close(score_to_elo(0.5), 0.0);
+    assert_close(score_to_elo(0.6), 70.437);
+    assert_close(elo_to_score(100.0), 0.640);
+    assert_close(elo_to_score(score_to_elo(0.25)), 0.25);
+
+    // the same 60% score, draws narrow the interval
+    let (elo, error) = score(60, 40, 0).elo().unwrap();
+    assert_close(elo, 70.437);
+    assert_close(error, 70.573);
+    let (elo, error) = score(30, 10, 60).elo().unwrap();
+    assert_close(elo, 70.437);
+    assert_close(error, 42.802);
+
+    assert!(score(0, 0, 0).elo().is_none());
+    assert!(score(5, 0, 0).elo().is_none());
+    assert!(score(0, 5, 0).elo().is_none());
+}
+
+#[test]
+fn results_count_for_the_first_engine() {
+    let mut score = MatchScore::default();
+    score.record(GameResult::WhiteWins, true);
+    score.record(GameResult::WhiteWins, false);
+    score.record(GameResult::BlackWins, false);
+    score.record(GameResult::Draw, false);
+    assert_eq!((score.wins, score.losses, score.draws), (2, 1, 1));
+}
+
+#[test]
+fn sprt_decisions() {
+    let sprt = Sprt {
+        elo0: 0.0,
+        elo1: 10.0,
+        alpha: 0.05,
+        beta: 0.05,
+    };
+    let (lower, upper) = sprt.bounds();
+    assert_close(lower, -2.944);
+    assert_close(upper, 2.944);
+
+    assert_close(sprt.llr(&score(600, 400, 1000)), 10.055);
+    assert_close(sprt.llr(&score(400, 600, 1000)), -13.434);
+    assert_close(sprt.llr(&score(110, 100, 290)), 0.192);
+    // no games, or all draws, say nothing yet
+    assert_eq!(sprt.llr(&score(0, 0, 0)), 0.0);
+    assert_eq!(sprt.llr(&score(0, 0, 50)), 0.0);
+
+    assert!(sprt.status(&score(600, 400, 1000)) == SprtStatus::AcceptH1);
+    assert!(sprt.status(&score(400, 600, 1000)) == SprtStatus::AcceptH0);
+    assert!(sprt.status(&score(110, 100, 290)) == SprtStatus::Continue);
+}
+
+#[test]
+fn pgn_marks_chess960_games() {
+    let game = |start_fen: String| PlayedGame {
+        start_fen,
+        sans: vec!["e4".to_string(), "e5".to_string()],
+        result: Some(GameResult::Draw),
+        termination: "move limit".to_string(),
+    };
+    let mut headers = PgnHeaders {
+        event: "test",
+        round: 1,
+        white: "one",
+        black: "two",
+        time_control: "10+0.1",
+        chess960: false,
+    };
+
+    let pgn = write_pgn(&headers, &game(STARTING_FEN.to_string()));
+    assert!(!pgn.contains("Variant") && !pgn.contains("FEN"));
+    assert!(pgn.contains("\n1. e4 e5 1/2-1/2\n"));
+
+    headers.chess960 = true;
+    let fen = chess960_fen(0);
+    let pgn = write_pgn(&headers, &game(fen.clone()));
+    assert!(pgn.contains("[Variant \"Chess960\"]\n[SetUp \"1\"]\n"));
+    assert!(pgn.contains(&format!("[FEN \"{fen}\"]")));
+}
+
+#[test]
+fn pgn_leaves_aborted_games_without_a_result() {
+    let game = PlayedGame {
+        start_fen: STARTING_FEN.to_string(),
+        sans: Vec::new(),
+        result: None,
+        termination: "engine failed to start a game".to_string(),
+    };
+    let headers = PgnHeaders {
+        event: "test",
+        round: 1,
+        white: "one",
+        black: "two",
+        time_control: "10+0.1",
+        chess960: false,
+    };
+    let pgn = write_pgn(&headers, &game);
+    assert!(pgn.contains("[Result \"*\"]\n"));
+    assert!(pgn.ends_with("\n*\n\n"));
+}
@@ -0,0 +1,59 @@
+use rustybattler::{Board, Move, find_mate, gen_legal_moves, move_to_uci, parse_uci};
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+// plays the line out, checking it ends in mate rather than just running out
+fn line_to_uci(board: &Board, line: &[Move]) -> Vec<String> {
+    let mut board = *board;
+    let uci = line
+        .iter()
+        .map(|&m| {
+            let uci = move_to_uci(&board, m);
+            board.make_move(m);
+            uci
+        })
+        .collect();
+    assert!(gen_legal_moves(&board).is_empty());
+    assert!(board.in_check(board.side_to_move()));
+    uci
+}
+
+#[test]
+fn mate_in_one() {
+    let board = board("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    let line = find_mate(&board, 1).unwrap();
+    assert_eq!(line_to_uci(&board, &line), ["a1a8"]);
+}
+
+#[test]
+fn mate_in_three_is_the_shortest_line() {
+    let board = board("7k/8/8/4K3/8/8/8/R7 w - - 0 1");
+    // allowed five moves, it still has to come back with the mate in three
+    let line = find_mate(&board, 5).unwrap();
+    assert_eq!(line.len(), 5);
+    assert_eq!(line_to_uci(&board, &line)[0], "e5f6");
+}
+
+#[test]
+fn nothing_within_one_move_too_few() {
+    let mate_in_two = board("7k/8/5K2/8/8/8/8/R7 w - - 0 1");
+    assert_eq!(find_mate(&mate_in_two, 1), None);
+    assert_eq!(find_mate(&mate_in_two, 2).map(|line| line.len()), Some(3));
+
+    let mate_in_three = board("7k/8/8/4K3/8/8/8/R7 w - - 0 1");
+    assert_eq!(find_mate(&mate_in_three, 2), None);
+}
+
+#[test]
+fn stalemate_is_not_mate() {
+    let board = board("k7/8/2Q5/8/8/8/8/7K w - - 0 1");
+    let mut stalemate = board;
+    stalemate.make_move(parse_uci(&board, "c6b6").unwrap());
+    assert!(gen_legal_moves(&stalemate).is_empty());
+    assert!(!stalemate.in_check(stalemate.side_to_move()));
+
+    assert_eq!(find_mate(&board, 2), None);
+    assert_eq!(find_mate(&stalemate, 2), None);
+}
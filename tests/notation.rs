@@ -0,0 +1,120 @@
+use rustybattler::notation::is_castling;
+use rustybattler::{Board, gen_legal_moves, move_to_san, move_to_uci, parse_san, parse_uci};
+
+// every legal move has to survive being written out and read back
+fn assert_uci_round_trips(board: &Board) {
+    for m in gen_legal_moves(board) {
+        let uci = move_to_uci(board, m);
+        assert_eq!(
+            parse_uci(board, &uci),
+            Some(m),
+            "{uci} in {}",
+            board.to_fen()
+        );
+    }
+}
+
+#[test]
+fn shredder_fen_castling_outside_chess960_mode() {
+    // the king on b1 lands on c1, one step away, so b1c1 would be the king move
+    let fen = "4k3/8/8/8/8/8/8/RK6 w A - 0 1";
+    let mut board = Board::from_fen(fen).unwrap();
+    assert!(board.chess960(), "only chess960 can castle from b1");
+    assert_uci_round_trips(&board);
+
+    // even when told it's standard chess, castling stays distinct from b1c1
+    board.set_chess960(false);
+    let castle = gen_legal_moves(&board)
+        .into_iter()
+        .find(|&m| is_castling(&board, m))
+        .unwrap();
+    assert_eq!(move_to_uci(&board, castle), "b1a1");
+    assert_uci_round_trips(&board);
+}
+
+#[test]
+fn standard_castling_stays_two_squares() {
+    let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    assert!(!board.chess960());
+    let uci: Vec<String> = gen_legal_moves(&board)
+        .into_iter()
+        .filter(|&m| is_castling(&board, m))
+        .map(|m| move_to_uci(&board, m))
+        .collect();
+    assert!(uci.contains(&"e1g1".to_string()) && uci.contains(&"e1c1".to_string()));
+    assert_uci_round_trips(&board);
+}
+
+// reads the SAN back with parse_san and checks it is the move that was written
+fn san(fen: &str, uci: &str) -> String {
+    let board = Board::from_fen(fen).unwrap();
+    let m = parse_uci(&board, uci).unwrap();
+    let san = move_to_san(&board, m);
+    assert_eq!(parse_san(&board, &san), Some(m), "{san} in {fen}");
+    san
+}
+
+#[test]
+fn san_castling() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    assert_eq!(san(fen, "e1g1"), "O-O");
+    assert_eq!(san(fen, "e1c1"), "O-O-O");
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(parse_san(&board, "0-0"), parse_uci(&board, "e1g1"));
+    assert_eq!(parse_san(&board, "0-0-0"), parse_uci(&board, "e1c1"));
+
+    // the king-takes-rook spelling in chess960 is still O-O
+    let fen = "4k3/8/8/8/8/8/8/RK6 w A - 0 1";
+    assert_eq!(san(fen, "b1a1"), "O-O-O");
+}
+
+#[test]
+fn san_disambiguation() {
+    // knights on b1 and f1 both reach d2, rooks on a1 and a5 both reach a3
+    let fen = "4k3/8/8/R7/8/8/8/RN2KN2 w - - 0 1";
+    assert_eq!(san(fen, "b1d2"), "Nbd2");
+    assert_eq!(san(fen, "a1a3"), "R1a3");
+    assert_eq!(san(fen, "a5a3"), "R5a3");
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(parse_san(&board, "Nd2"), None, "ambiguous");
+
+    // queens on d4, f4 and f2 all reach e3, the one on f4 needs file and rank
+    let fen = "k7/8/8/8/3Q1Q2/8/5Q2/4K3 w - - 0 1";
+    assert_eq!(san(fen, "f4e3"), "Qf4e3");
+    assert_eq!(san(fen, "d4e3"), "Qde3");
+    assert_eq!(san(fen, "f2e3"), "Q2e3");
+}
+
+#[test]
+fn san_pawns_and_promotions() {
+    let fen = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3";
+    assert_eq!(san(fen, "e5f6"), "exf6");
+    assert_eq!(san(fen, "e5e6"), "e6");
+
+    let fen = "1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+    assert_eq!(san(fen, "a7a8q"), "a8=Q");
+    assert_eq!(san(fen, "a7b8q"), "axb8=Q+");
+    assert_eq!(san(fen, "a7b8n"), "axb8=N");
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(parse_san(&board, "a8Q"), parse_uci(&board, "a7a8q"));
+    assert_eq!(parse_san(&board, "a8"), None, "promotion needs a piece");
+}
+
+#[test]
+fn san_check_and_mate() {
+    // fool's mate
+    let fen = "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2";
+    assert_eq!(san(fen, "d8h4"), "Qh4#");
+    assert_eq!(san(fen, "f8c5"), "Bc5");
+
+    let fen = "4k3/8/8/8/8/8/8/R3K3 w Q - 0 1";
+    assert_eq!(san(fen, "a1a8"), "Ra8+");
+    assert_eq!(san(fen, "e1c1"), "O-O-O");
+
+    // annotation suffixes are ignored when reading
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(parse_san(&board, "Ra8+!?"), parse_uci(&board, "a1a8"));
+    assert_eq!(parse_san(&board, "Rxa8"), parse_uci(&board, "a1a8"));
+    assert_eq!(parse_san(&board, "Ra9"), None);
+    assert_eq!(parse_san(&board, "Nc3"), None);
+}
@@ -0,0 +1,79 @@
+use rustybattler::{Board, gen_legal_moves};
+
+fn perft(board: &Board, depth: u32) -> u64 {
+    let moves = gen_legal_moves(board);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    let mut nodes = 0;
+    for m in moves {
+        let mut next = *board;
+        next.make_move(m);
+        nodes += perft(&next, depth - 1);
+    }
+    nodes
+}
+
+fn assert_perft(fen: &str, depth: u32, expected: u64) {
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(perft(&board, depth), expected, "perft({depth}) of {fen}");
+}
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn standard_positions() {
+    assert_perft(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        4,
+        197281,
+    );
+    assert_perft(KIWIPETE, 3, 97862);
+    assert_perft("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4, 43238);
+    assert_perft(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        3,
+        9467,
+    );
+    assert_perft(
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        3,
+        62379,
+    );
+    assert_perft(
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        3,
+        89890,
+    );
+}
+
+// castling with the rooks and king off their usual squares, in Shredder-FEN
+#[test]
+fn chess960_positions() {
+    assert_perft(
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        3,
+        12189,
+    );
+    assert_perft(
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        3,
+        18002,
+    );
+    assert_perft(
+        "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        3,
+        10471,
+    );
+    assert_perft(
+        "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        3,
+        13440,
+    );
+}
+
+#[test]
+#[ignore = "slow in debug builds, run with --release -- --ignored"]
+fn kiwipete_depth_4() {
+    assert_perft(KIWIPETE, 4, 4085603);
+}
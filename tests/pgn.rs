@@ -0,0 +1,104 @@
+use rustybattler::pgn::parse_pgn;
+
+#[test]
+fn stray_parenthesis_does_not_swallow_later_games() {
+    let text = r#"[Event "one"]
+
+1. e4 e5 1-0
+
+[Event "two"]
+
+1. d4 ) d5 0-1
+
+[Event "three"]
+
+1. c4 c5 1/2-1/2
+"#;
+    let games = parse_pgn(text);
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[1].header("Event"), Some("two"));
+    assert_eq!(games[1].moves, ["d4", "d5"]);
+    assert_eq!(games[2].header("Event"), Some("three"));
+    assert_eq!(games[2].moves, ["c4", "c5"]);
+    assert_eq!(games[2].result, "1/2-1/2");
+}
+
+#[test]
+fn unclosed_variation_ends_with_the_game() {
+    let text = r#"[Event "one"]
+
+1. e4 (1. d4 d5 e5 *
+
+[Event "two"]
+
+1. c4 c5 1-0
+"#;
+    let games = parse_pgn(text);
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].moves, ["e4"]);
+    assert_eq!(games[1].header("Event"), Some("two"));
+    assert_eq!(games[1].moves, ["c4", "c5"]);
+}
+
+#[test]
+fn headers_and_movetext() {
+    let text = r#"[Event "Casual game"]
+[White "Anderssen, Adolf"]
+[Black "Kieseritzky, Lionel"]
+[Result "1-0"]
+
+1. e4 e5 2. f4 {the King's Gambit} exf4 $1 3. Bc4!? Qh4+ ; the queen comes out
+4. Kf1 b5 (4... Nf6 5. Nf3) 5. Bxb5 Nf6 6... Qh5 1-0
+"#;
+    let games = parse_pgn(text);
+    assert_eq!(games.len(), 1);
+    let game = &games[0];
+    assert_eq!(game.headers.len(), 4);
+    assert_eq!(game.header("White"), Some("Anderssen, Adolf"));
+    assert_eq!(game.header("Round"), None);
+    assert_eq!(
+        game.moves,
+        [
+            "e4", "e5", "f4", "exf4", "Bc4!?", "Qh4+", "Kf1", "b5", "Bxb5", "Nf6", "Qh5"
+        ]
+    );
+    assert_eq!(game.result, "1-0");
+}
+
+#[test]
+fn comments_and_variations_span_lines() {
+    let text = r#"[Event "one"]
+
+1. d4 {a comment
+that goes on (with a parenthesis} d5 2. c4 (2. Nf3 {inside} (2. Bf4 e6)
+Nf6) 2... c6 0-1
+"#;
+    let games = parse_pgn(text);
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].moves, ["d4", "d5", "c4", "c6"]);
+    assert_eq!(games[0].result, "0-1");
+}
+
+#[test]
+fn games_without_results() {
+    // the second game starts before the first one gave a result, the
+    // last one just runs out of text
+    let text = r#"[Event "one"]
+
+1. e4 e5
+[Event "two"]
+
+1. d4 *
+
+[Event "three"]
+
+1. c4
+"#;
+    let games = parse_pgn(text);
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[0].moves, ["e4", "e5"]);
+    assert_eq!(games[0].result, "*");
+    assert_eq!(games[1].moves, ["d4"]);
+    assert_eq!(games[2].moves, ["c4"]);
+    assert_eq!(games[2].result, "*");
+}
@@ -0,0 +1,173 @@
+use rustybattler::pgn::parse_pgn;
+use rustybattler::polyglot::book::{BookMove, BookSelection, PolyglotBook};
+use rustybattler::polyglot::builder::{BookBuildOptions, BookBuilder};
+use rustybattler::polyglot::polyglot_key;
+use rustybattler::{Board, STARTING_FEN, Square, parse_uci};
+
+// the worked examples from the Polyglot book format description: each line
+// plays one more move from the start position
+const REFERENCE_KEYS: [(&str, &str, u64); 9] = [
+    (
+        "",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        0x463b96181691fc9c,
+    ),
+    (
+        "e2e4",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        0x823c9b50fd114196,
+    ),
+    (
+        "e2e4 d7d5",
+        "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+        0x0756b94461c50fb0,
+    ),
+    (
+        "e2e4 d7d5 e4e5",
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2",
+        0x662fafb965db29d4,
+    ),
+    (
+        "e2e4 d7d5 e4e5 f7f5",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        0x22a48b5a8e47ff78,
+    ),
+    (
+        "e2e4 d7d5 e4e5 f7f5 e1e2",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3",
+        0x652a607ca3f242c1,
+    ),
+    (
+        "e2e4 d7d5 e4e5 f7f5 e1e2 e8f7",
+        "rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4",
+        0x00fdd303c946bdd9,
+    ),
+    (
+        "a2a4 b7b5 h2h4 b5b4 c2c4",
+        "rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3",
+        0x3c8123ea7b067637,
+    ),
+    (
+        "a2a4 b7b5 h2h4 b5b4 c2c4 b4c3 a1a3",
+        "rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4",
+        0x5c3f9b829b279560,
+    ),
+];
+
+#[test]
+fn reference_keys_from_fen() {
+    for (_, fen, key) in REFERENCE_KEYS {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(polyglot_key(&board), key, "{fen}");
+    }
+}
+
+#[test]
+fn reference_keys_after_moves() {
+    for (moves, fen, key) in REFERENCE_KEYS {
+        let mut board = Board::from_fen(STARTING_FEN).unwrap();
+        for uci in moves.split_whitespace() {
+            let m = parse_uci(&board, uci).unwrap();
+            board.make_move(m);
+        }
+        // the published FENs leave the halfmove clocks at 0, so skip the counters
+        let fields = |fen: &str| fen.split(' ').take(4).collect::<Vec<_>>().join(" ");
+        assert_eq!(fields(&board.to_fen()), fields(fen));
+        assert_eq!(polyglot_key(&board), key, "{moves}");
+    }
+}
+
+fn sq(s: &str) -> Square {
+    s.parse().unwrap()
+}
+
+// polyglot stores castling as king-takes-rook, we hand out the king's own move
+#[test]
+fn castling_moves_round_trip() {
+    let white = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let black = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+    let cases = [
+        (&white, "e1", "h1", "g1"),
+        (&white, "e1", "a1", "c1"),
+        (&black, "e8", "h8", "g8"),
+        (&black, "e8", "a8", "c8"),
+    ];
+
+    for (board, from, rook, king_to) in cases {
+        let raw = (sq(rook).index() | sq(from).index() << 6) as u16;
+        let decoded = BookMove::decode(raw, board);
+        assert_eq!(decoded.to_uci(), format!("{from}{king_to}"));
+        assert_eq!(decoded.encode(board), raw);
+    }
+}
+
+#[test]
+fn chess960_castling_stays_king_takes_rook() {
+    let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    board.set_chess960(true);
+    let raw = (sq("h1").index() | sq("e1").index() << 6) as u16;
+    let decoded = BookMove::decode(raw, &board);
+    assert_eq!(decoded.to_uci(), "e1h1");
+    assert_eq!(decoded.encode(&board), raw);
+}
+
+// a standard-chess game castling both ways has to end up as e1h1 and e8a8 in
+// the book, and come back out as the king's own move
+#[test]
+fn built_book_castles_king_takes_rook() {
+    let pgn =
+        "[Result \"1/2-1/2\"]\n\n1. e4 d5 2. Nf3 Qd6 3. Bc4 Bd7 4. O-O Nc6 5. d3 O-O-O 1/2-1/2\n";
+    let mut builder = BookBuilder::new(BookBuildOptions::default());
+    assert!(builder.add_game(&parse_pgn(pgn)[0]));
+    let bytes: Vec<u8> = builder
+        .entries()
+        .iter()
+        .flat_map(|e| e.to_bytes())
+        .collect();
+    let book = PolyglotBook::from_bytes(&bytes).unwrap();
+
+    let cases = [
+        (
+            "rn2kbnr/pppbpppp/3q4/3p4/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+            "e1",
+            "h1",
+            "e1g1",
+        ),
+        (
+            "r3kbnr/pppbpppp/2nq4/3p4/2B1P3/3P1N2/PPP2PPP/RNBQ1RK1 b kq - 0 5",
+            "e8",
+            "a8",
+            "e8c8",
+        ),
+    ];
+    for (fen, king, rook, uci) in cases {
+        let board = Board::from_fen(fen).unwrap();
+        let entries = book.probe(&board);
+        assert_eq!(entries.len(), 1, "{fen}");
+        assert_eq!(
+            entries[0].mv,
+            (sq(rook).index() | sq(king).index() << 6) as u16
+        );
+
+        let picked = book.pick(&board, BookSelection::BestWeight).unwrap();
+        assert_eq!(picked.to_uci(), uci);
+        let m = parse_uci(&board, uci).unwrap();
+        assert!(BookMove::from_castling(&board, m) == picked);
+    }
+}
+
+#[test]
+fn ordinary_moves_round_trip() {
+    let board = Board::from_fen("4k3/1P6/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    for (uci, raw) in [
+        ("e1f1", 0b000_000100_000101),
+        ("h1h8", 0b000_000111_111111),
+        // promotion piece in bits 12-14, queen is 4
+        ("b7b8q", 0b100_110001_111001),
+        ("b7b8n", 0b001_110001_111001),
+    ] {
+        let decoded = BookMove::decode(raw, &board);
+        assert_eq!(decoded.to_uci(), uci);
+        assert_eq!(decoded.encode(&board), raw);
+    }
+}
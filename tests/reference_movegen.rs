@@ -0,0 +1,127 @@
+use rustybattler::movegen::reference::gen_reference_moves;
+use rustybattler::{Board, Move, chess960_fen, gen_legal_moves, move_to_uci};
+
+// the usual perft suite: start position, kiwipete, the rook endgame,
+// promotions and the two castling-heavy middlegames
+const PERFT_POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+];
+
+// chess960 middlegames, castling rights given as rook files
+const CHESS960_POSITIONS: [&str; 4] = [
+    "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+    "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+    "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+    "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+];
+
+// returns the legal moves, panicking with the moves only one side found
+fn compare(board: &Board) -> Vec<Move> {
+    let mut fast = gen_legal_moves(board);
+    let mut slow = gen_reference_moves(board);
+    fast.sort_unstable();
+    slow.sort_unstable();
+
+    if fast != slow {
+        let only = |a: &[Move], b: &[Move]| {
+            a.iter()
+                .filter(|m| !b.contains(m))
+                .map(|&m| move_to_uci(board, m))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        panic!(
+            "{}\nonly bitboards: {}\nonly reference: {}",
+            board.to_fen(),
+            only(&fast, &slow),
+            only(&slow, &fast)
+        );
+    }
+    fast
+}
+
+// checks every node of the tree, returns how many there were
+fn walk(board: &Board, depth: u32) -> u64 {
+    let moves = compare(board);
+    if depth == 0 {
+        return 1;
+    }
+    1 + moves
+        .into_iter()
+        .map(|m| {
+            let mut after = *board;
+            after.make_move(m);
+            walk(&after, depth - 1)
+        })
+        .sum::<u64>()
+}
+
+fn load(fen: &str, chess960: bool) -> Board {
+    let mut board = Board::from_fen(fen).unwrap();
+    board.set_chess960(chess960);
+    board
+}
+
+#[test]
+fn perft_trees_agree() {
+    let nodes: u64 = PERFT_POSITIONS
+        .iter()
+        .map(|fen| walk(&load(fen, false), 2))
+        .sum();
+    // the published perft(0) + perft(1) + perft(2) of the six positions
+    assert_eq!(nodes, 6 + 178 + 6459);
+}
+
+#[test]
+fn chess960_trees_agree() {
+    let nodes: u64 = [0, 1, 100, 517, 959]
+        .into_iter()
+        .map(|index| walk(&load(&chess960_fen(index), true), 2))
+        .sum();
+    // every start position has 20 moves and 400 replies
+    assert_eq!(nodes, 5 * (1 + 20 + 400));
+
+    let nodes: u64 = CHESS960_POSITIONS
+        .iter()
+        .map(|fen| walk(&load(fen, true), 2))
+        .sum();
+    assert_eq!(nodes, 4 + (21 + 21 + 20 + 22) + (528 + 807 + 479 + 593));
+}
+
+#[test]
+fn random_games_agree() {
+    // xorshift with a fixed seed, so a failure can be reproduced
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut positions = 0;
+    for game in 0..100 {
+        // every other game from the standard start, the rest chess960
+        let index = if game % 2 == 0 {
+            518
+        } else {
+            (next() % 960) as u16
+        };
+        let mut board = load(&chess960_fen(index), true);
+
+        for _ in 0..200 {
+            let moves = compare(&board);
+            positions += 1;
+            if moves.is_empty() || board.fifty_move_counter() >= 100 {
+                break;
+            }
+            board.make_move(moves[next() as usize % moves.len()]);
+        }
+    }
+    assert!(positions > 10_000);
+}
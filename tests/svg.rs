@@ -0,0 +1,74 @@
+use rustybattler::{Board, Color, Square, SvgOptions};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn sq(s: &str) -> Square {
+    s.parse().unwrap()
+}
+
+#[test]
+fn pieces_and_squares() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let svg = board.to_svg(&SvgOptions::default());
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect ").count(), 64);
+    // every glyph asks for text presentation so the fill applies
+    assert_eq!(svg.matches('\u{fe0e}').count(), 32);
+    assert_eq!(svg.matches("fill=\"white\" stroke").count(), 16);
+    assert_eq!(svg.matches("fill=\"black\" stroke").count(), 16);
+    assert_eq!(svg.matches('♟').count(), 16);
+    assert!(!svg.contains("<line "));
+    assert!(!svg.contains("<circle "));
+}
+
+#[test]
+fn highlights_marks_and_arrows() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let options = SvgOptions {
+        // e5 is a dark square, d5 a light one
+        highlight: sq("e5").bb() | sq("d5").bb(),
+        marks: board.get_knight_targets(Color::White),
+        arrows: vec![(sq("e5"), sq("f7")), (sq("a1"), sq("a1"))],
+        ..SvgOptions::default()
+    };
+    let svg = board.to_svg(&options);
+
+    assert_eq!(svg.matches("fill=\"#aaa23a\"").count(), 1);
+    assert_eq!(svg.matches("fill=\"#cdd26a\"").count(), 1);
+    assert_eq!(
+        svg.matches("<circle ").count(),
+        board.get_knight_targets(Color::White).popcount() as usize
+    );
+    // a zero-length arrow is dropped
+    assert_eq!(svg.matches("<line ").count(), 1);
+    assert!(svg.contains("marker-end=\"url(#arrowhead)\""));
+    // from the middle of e5, 45px squares
+    assert!(svg.contains("<line x1=\"202.5\" y1=\"157.5\""));
+}
+
+#[test]
+fn coordinates_follow_orientation() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let plain = board.to_svg(&SvgOptions::default());
+    let flipped = board.to_svg(&SvgOptions {
+        flipped: true,
+        ..SvgOptions::default()
+    });
+
+    // the 8th rank label sits at the top unless the board is flipped. a8 and
+    // h1 are both light squares, so the label is in the dark colour
+    assert!(plain.contains(
+        "y=\"11.2\" font-size=\"11.2\" font-family=\"sans-serif\" fill=\"#b58863\">8</text>"
+    ));
+    assert!(flipped.contains(
+        "y=\"11.2\" font-size=\"11.2\" font-family=\"sans-serif\" fill=\"#b58863\">1</text>"
+    ));
+
+    let none = board.to_svg(&SvgOptions {
+        coordinates: false,
+        ..SvgOptions::default()
+    });
+    assert!(!none.contains("sans-serif"));
+}
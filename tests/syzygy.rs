@@ -0,0 +1,411 @@
+use std::path::Path;
+
+use rustybattler::syzygy::{ProbeError, Tablebase, Wdl};
+use rustybattler::{
+    Board, Color, Move, MoveExt, PieceType, STARTING_FEN, Square, find_mate, gen_legal_moves,
+};
+
+// generated by a retrograde pass rather than downloaded, the published
+// tables for the same endings can replace them file for file
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/syzygy");
+
+fn tablebase() -> Tablebase {
+    Tablebase::open(FIXTURES).unwrap()
+}
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+// the same position with the colours swapped, which has to probe the same
+fn mirrored(board: &Board) -> Board {
+    let fen = board.to_fen();
+    let fields: Vec<&str> = fen.split(' ').collect();
+    let placement: Vec<String> = fields[0]
+        .split('/')
+        .rev()
+        .map(|rank| {
+            rank.chars()
+                .map(|c| {
+                    if c.is_ascii_uppercase() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        c.to_ascii_uppercase()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let side = if fields[1] == "w" { "b" } else { "w" };
+    Board::from_fen(&format!("{} {side} - - 0 1", placement.join("/"))).unwrap()
+}
+
+fn zeroing(m: Move) -> bool {
+    m.captured() != PieceType::None || m.mover() == PieceType::Pawn
+}
+
+fn mated(board: &Board) -> bool {
+    board.in_check(board.side_to_move()) && gen_legal_moves(board).is_empty()
+}
+
+// the stored values have to agree with what the moves lead to
+fn assert_consistent(tb: &Tablebase, board: &Board) {
+    let fen = board.to_fen();
+    let wdl = tb.probe_wdl(board).unwrap();
+    let dtz = tb.probe_dtz(board).unwrap();
+    assert_eq!(tb.probe_wdl(&mirrored(board)).unwrap(), wdl, "{fen}");
+    assert_eq!(tb.probe_dtz(&mirrored(board)).unwrap(), dtz, "{fen}");
+
+    let mut best = Wdl::Loss;
+    let mut fastest_win = None;
+    let mut longest_loss = 0;
+    for m in gen_legal_moves(board) {
+        let mut after = *board;
+        after.make_move(m);
+        let value = -tb.probe_wdl(&after).unwrap();
+        best = best.max(value);
+
+        let plies = if zeroing(m) || mated(&after) {
+            1
+        } else {
+            1 + tb.probe_dtz(&after).unwrap().abs()
+        };
+        if value == Wdl::Win {
+            fastest_win = Some(fastest_win.map_or(plies, |f: i32| f.min(plies)));
+        }
+        longest_loss = longest_loss.max(plies);
+    }
+
+    if gen_legal_moves(board).is_empty() {
+        let expected = if board.in_check(board.side_to_move()) {
+            (Wdl::Loss, -1)
+        } else {
+            (Wdl::Draw, 0)
+        };
+        assert_eq!((wdl, dtz), expected, "{fen}");
+        return;
+    }
+    assert_eq!(wdl, best, "{fen}");
+    match wdl {
+        Wdl::Win => assert_eq!(Some(dtz), fastest_win, "{fen}"),
+        Wdl::Loss => assert_eq!(dtz, -longest_loss, "{fen}"),
+        _ => assert_eq!(dtz, 0, "{fen}"),
+    }
+}
+
+// a board holding only the given pieces, as FEN letters on square indices
+fn place(pieces: &[(char, usize)], side: Color) -> Board {
+    let mut grid = [None; 64];
+    for &(piece, sq) in pieces {
+        grid[sq] = Some(piece);
+    }
+    let ranks: Vec<String> = (0..8)
+        .rev()
+        .map(|rank| {
+            let mut fen = String::new();
+            let mut empty = 0;
+            for piece in &grid[rank * 8..rank * 8 + 8] {
+                match piece {
+                    Some(c) => {
+                        if empty > 0 {
+                            fen += &empty.to_string();
+                            empty = 0;
+                        }
+                        fen.push(*c);
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen += &empty.to_string();
+            }
+            fen
+        })
+        .collect();
+    let side = if side == Color::White { "w" } else { "b" };
+    board(&format!("{} {side} - - 0 1", ranks.join("/")))
+}
+
+// xorshift with a fixed seed, so a failure can be reproduced
+fn random_positions(white: &str, black: &str, count: usize) -> Vec<Board> {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let black = black.to_lowercase();
+    let mut positions = Vec::new();
+    while positions.len() < count {
+        let side = if next() % 2 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let letters = white.chars().chain(black.chars());
+        let pieces: Vec<(char, usize)> = letters.map(|c| (c, (next() % 64) as usize)).collect();
+        let clash = pieces
+            .iter()
+            .enumerate()
+            .any(|(i, &(_, sq))| pieces[..i].iter().any(|&(_, other)| other == sq));
+        let pawn_on_edge = pieces
+            .iter()
+            .any(|&(c, sq)| c.eq_ignore_ascii_case(&'p') && !(8..56).contains(&sq));
+        if clash || pawn_on_edge {
+            continue;
+        }
+        let board = place(&pieces, side);
+        if !board.in_check(side.opponent()) {
+            positions.push(board);
+        }
+    }
+    positions
+}
+
+#[test]
+fn values_agree_with_the_moves() {
+    let tb = tablebase();
+    for white in ["KQ", "KR", "KP", "KNN", "KB"] {
+        for board in random_positions(white, "K", 300) {
+            assert_consistent(&tb, &board);
+        }
+    }
+}
+
+#[test]
+fn short_mates_match_the_mate_finder() {
+    let tb = tablebase();
+    let mut mates = 0;
+    for white in ["KQ", "KR"] {
+        for board in random_positions(white, "K", 300) {
+            if board.side_to_move() != Color::White {
+                continue;
+            }
+            // nothing can zero the count on the way, so it runs all the way to mate
+            let dtz = tb.probe_dtz(&board).unwrap();
+            let found = find_mate(&board, 2).map(|line| line.len() as i32);
+            if dtz == 1 || dtz == 3 {
+                assert_eq!(found, Some(dtz), "{}", board.to_fen());
+                mates += 1;
+            } else {
+                assert_eq!(found, None, "{}", board.to_fen());
+            }
+        }
+    }
+    assert!(mates > 0);
+}
+
+#[test]
+fn known_positions() {
+    let tb = tablebase();
+    let cases = [
+        // stalemate, and black taking the undefended queen
+        ("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", Wdl::Draw, 0),
+        ("8/8/8/8/8/2k5/2Q5/7K b - - 0 1", Wdl::Draw, 0),
+        // mated, and mate in one
+        ("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1", Wdl::Loss, -1),
+        ("7k/8/6K1/8/8/8/8/5Q2 w - - 0 1", Wdl::Win, 1),
+        ("k7/8/1K6/8/8/8/8/7R w - - 0 1", Wdl::Win, 1),
+        // the pawn only draws against a king in front of it
+        ("8/8/8/8/8/3k4/3P4/3K4 w - - 0 1", Wdl::Draw, 0),
+        ("8/8/8/8/8/3k4/3P4/3K4 b - - 0 1", Wdl::Draw, 0),
+        ("8/3P4/8/8/8/8/k7/3K4 w - - 0 1", Wdl::Win, 1),
+        // and the same with black as the stronger side
+        ("3k4/3p4/3K4/8/8/8/8/8 w - - 0 1", Wdl::Draw, 0),
+        ("3k4/K7/8/8/8/8/3p4/8 b - - 0 1", Wdl::Win, 1),
+        // two knights can't force mate, but can mate a king that walks in
+        ("8/8/8/8/3k4/8/8/NN2K3 w - - 0 1", Wdl::Draw, 0),
+        ("k7/3N4/1K6/3N4/8/8/8/8 w - - 0 1", Wdl::Win, 1),
+        ("8/8/8/8/8/8/8/K1kB4 w - - 0 1", Wdl::Draw, 0),
+    ];
+    for (fen, wdl, dtz) in cases {
+        let board = board(fen);
+        assert_eq!(tb.probe_wdl(&board), Ok(wdl), "{fen}");
+        assert_eq!(tb.probe_dtz(&board), Ok(dtz), "{fen}");
+    }
+
+    // two kings need no table at all
+    let empty = Tablebase::new();
+    assert_eq!(
+        empty.probe_wdl(&board("8/8/8/3k4/8/3K4/8/8 w - - 0 1")),
+        Ok(Wdl::Draw)
+    );
+}
+
+// the longest DTZ with white to move. nothing zeroes the count before
+// mate, so it's one ply short of twice the longest forced mate
+fn longest_mate(piece: char) -> i32 {
+    let tb = tablebase();
+    let mut deepest = 0;
+    // by symmetry the white king can stay in the a1-d1-d4 triangle
+    for king in ["a1", "b1", "c1", "d1", "b2", "c2", "d2", "c3", "d3", "d4"] {
+        let king: Square = king.parse().unwrap();
+        for (other, black) in Square::all().flat_map(|a| Square::all().map(move |b| (a, b))) {
+            if other == king || black == king || black == other {
+                continue;
+            }
+            let pieces = [
+                ('K', king.index()),
+                (piece, other.index()),
+                ('k', black.index()),
+            ];
+            let board = place(&pieces, Color::White);
+            if !board.in_check(Color::Black) {
+                deepest = deepest.max(tb.probe_dtz(&board).unwrap());
+            }
+        }
+    }
+    deepest
+}
+
+// the classical results: mate in 10 with a queen, in 16 with a rook
+#[test]
+fn longest_queen_mate() {
+    assert_eq!(longest_mate('Q'), 19);
+}
+
+#[test]
+#[ignore = "slow in debug builds, run with --release -- --ignored"]
+fn longest_rook_mate() {
+    assert_eq!(longest_mate('R'), 31);
+}
+
+// king and pawn endings any endgame book has
+#[test]
+fn textbook_pawn_endings() {
+    let tb = tablebase();
+    let cases = [
+        // the king on the sixth in front of its pawn wins whoever moves,
+        // results are for the side to move
+        ("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1", Wdl::Win),
+        ("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1", Wdl::Loss),
+        // pushing to the seventh with a check only stalemates
+        ("4k3/8/4P3/4K3/8/8/8/8 w - - 0 1", Wdl::Draw),
+        ("4k3/4P3/4K3/8/8/8/8/8 b - - 0 1", Wdl::Draw),
+        // a rook pawn can't drive the king out of the corner
+        ("k7/8/8/8/8/1K6/P7/8 w - - 0 1", Wdl::Draw),
+        // and a king outside the square of the pawn can't catch it
+        ("7k/8/8/8/P7/8/8/K7 w - - 0 1", Wdl::Win),
+        ("7k/8/8/8/P7/8/8/K7 b - - 0 1", Wdl::Loss),
+    ];
+    for (fen, wdl) in cases {
+        assert_eq!(tb.probe_wdl(&board(fen)), Ok(wdl), "{fen}");
+    }
+}
+
+#[test]
+fn unusable_positions_and_tables() {
+    let tb = tablebase();
+    assert_eq!(tb.max_pieces(), 4);
+
+    let start = board(STARTING_FEN);
+    assert_eq!(tb.probe_wdl(&start), Err(ProbeError::Castling));
+    let many = board("4k3/8/8/8/8/8/3PPP2/4K3 w - - 0 1");
+    assert_eq!(
+        tb.probe_wdl(&many),
+        Err(ProbeError::MissingTable("KPPPvK.rtbw".to_string()))
+    );
+    // the bishop ending is all draws, so there's no DTZ table, and it isn't needed
+    let bishop = board("8/8/8/8/8/2k5/8/K1B5 w - - 0 1");
+    assert_eq!(tb.probe_dtz(&bishop), Ok(0));
+
+    let bytes = fixture("KQvK.rtbw");
+    let files = [
+        ("KQvK.rtbw", bytes[..bytes.len() / 2].to_vec()),
+        ("KRvK.rtbw", b"not a table".to_vec()),
+    ];
+    with_tables(&files, |bad| {
+        for fen in [
+            "8/8/8/3k4/8/3K4/8/7Q w - - 0 1",
+            "8/8/8/3k4/8/3K4/8/7R w - - 0 1",
+        ] {
+            let err = bad.probe_wdl(&board(fen)).unwrap_err();
+            assert!(matches!(err, ProbeError::BadTable(_)), "{err}");
+        }
+    });
+}
+
+fn fixture(name: &str) -> Vec<u8> {
+    std::fs::read(Path::new(FIXTURES).join(name)).unwrap()
+}
+
+// a tablebase over a scratch directory holding the given files
+fn with_tables(files: &[(&str, Vec<u8>)], probe: impl FnOnce(&Tablebase)) {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static DIRS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "syzygy-{}-{}",
+        std::process::id(),
+        DIRS.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, bytes) in files {
+        std::fs::write(dir.join(name), bytes).unwrap();
+    }
+    let mut tb = Tablebase::new();
+    assert_eq!(tb.add_directory(&dir).unwrap(), files.len());
+    probe(&tb);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn corrupt_tables_are_errors() {
+    let queen = "8/8/8/3k4/8/3K4/8/7Q b - - 0 1";
+    let wdl = fixture("KQvK.rtbw");
+    let corrupt = |at: usize, values: &[u8]| {
+        let mut bytes = wdl.clone();
+        bytes[at..at + values.len()].copy_from_slice(values);
+        bytes
+    };
+    // black to move is the first compressed sub-table: a block size of 2^200,
+    // and lowest symbols that make the code lengths underflow
+    for bytes in [corrupt(13, &[200]), corrupt(22, &[0, 0, 0xff, 0xff])] {
+        with_tables(&[("KQvK.rtbw", bytes)], |tb| {
+            let err = tb.probe_wdl(&board(queen)).unwrap_err();
+            assert!(err.to_string().ends_with("corrupt table"), "{err}");
+        });
+    }
+
+    // any byte of the headers can be wrong, which must never panic
+    let dtz = fixture("KRvK.rtbz");
+    let rook = [
+        "8/8/8/3k4/8/3K4/8/7R w - - 0 1",
+        "8/8/8/3k4/8/3K4/8/7R b - - 0 1",
+    ];
+    let mut errors = 0;
+    for (name, bytes, fens) in [
+        ("KQvK.rtbw", &wdl, &[queen][..]),
+        ("KRvK.rtbz", &dtz, &rook),
+    ] {
+        for at in 4..256 {
+            for flip in [0x01, 0x10, 0xff] {
+                let mut bytes = bytes.clone();
+                bytes[at] ^= flip;
+                let files = [(name, bytes), ("KRvK.rtbw", fixture("KRvK.rtbw"))];
+                let files = if name == "KQvK.rtbw" {
+                    &files[..1]
+                } else {
+                    &files
+                };
+                with_tables(files, |tb| {
+                    for fen in fens {
+                        let board = board(fen);
+                        let result = if name.ends_with("rtbw") {
+                            tb.probe_wdl(&board).map(|_| 0)
+                        } else {
+                            tb.probe_dtz(&board)
+                        };
+                        match result {
+                            Ok(_) | Err(ProbeError::BadTable(_)) => {}
+                            Err(e) => panic!("{name} byte {at}: {e}"),
+                        }
+                        errors += result.is_err() as usize;
+                    }
+                });
+            }
+        }
+    }
+    assert!(errors > 0);
+}
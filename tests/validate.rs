@@ -0,0 +1,71 @@
+use rustybattler::{Board, CastleSide, Color, PositionError, STARTING_FEN, Square};
+
+fn sq(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+#[test]
+fn impossible_positions() {
+    use PositionError::*;
+    let cases = [
+        (
+            "4k3/8/8/8/8/8/8/8 w - - 0 1",
+            vec![KingCount(Color::White, 0)],
+        ),
+        (
+            "4k2k/8/8/8/8/8/8/4K3 w - - 0 1",
+            vec![KingCount(Color::Black, 2)],
+        ),
+        (
+            "4k3/8/8/8/8/8/8/P3K3 w - - 0 1",
+            vec![PawnOnBackRank(sq("a1"))],
+        ),
+        (
+            "3pk3/8/8/8/8/8/8/4K3 w - - 0 1",
+            vec![PawnOnBackRank(sq("d8"))],
+        ),
+        ("4k3/8/8/8/8/8/8/4RK2 w - - 0 1", vec![OpponentInCheck]),
+        (
+            "r3k3/8/8/8/8/8/8/4K3 w k - 0 1",
+            vec![BadCastlingRights(Color::Black, CastleSide::King)],
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K2R w Q - 0 1",
+            vec![BadCastlingRights(Color::White, CastleSide::Queen)],
+        ),
+        (
+            "4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1",
+            vec![BadEnPassant(sq("e3"))],
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w - d6 0 1",
+            vec![BadEnPassant(sq("d6"))],
+        ),
+        (
+            "4k3/8/8/8/8/P7/PPPPPPPP/4K3 w - - 0 1",
+            vec![TooManyPieces(Color::White)],
+        ),
+        (
+            "qq2k3/pppppppp/8/8/8/8/8/4K3 w - - 0 1",
+            vec![TooManyPieces(Color::Black)],
+        ),
+    ];
+    for (fen, errors) in cases {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.validate(), Err(errors), "{fen}");
+    }
+}
+
+#[test]
+fn legal_positions() {
+    for fen in [
+        STARTING_FEN,
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3",
+        "r1bq1rk1/2p1bppp/p1np1n2/1p2p3/4P3/1BP2N1P/PP1P1PP1/RNBQR1K1 b - - 0 9",
+        "2r3k1/pp3pp1/4p2p/3q4/3P4/P4Q1P/1P3PP1/2R3K1 w - - 0 25",
+    ] {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.validate(), Ok(()), "{fen}");
+    }
+}